@@ -0,0 +1,102 @@
+use crate::*;
+
+/// Binary image packed into one bit per pixel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitImage {
+    size: Size,
+    data: Vec<u8>,
+}
+
+impl BitImage {
+    /// Create a new `BitImage` with every bit cleared
+    pub fn new(size: impl Into<Size>) -> BitImage {
+        let size = size.into();
+        let n = size.width * size.height;
+        BitImage {
+            size,
+            data: vec![0; n.div_ceil(8)],
+        }
+    }
+
+    /// Create a `BitImage` from a single-channel image, values at or above 0.5 (normalized) are
+    /// set
+    pub fn from_image<T: Type>(image: &Image<T, Gray>) -> BitImage {
+        let mut dest = BitImage::new(image.size());
+        for (i, x) in image.data().iter().enumerate() {
+            if x.to_norm() >= 0.5 {
+                dest.data[i >> 3] |= 1 << (i & 7);
+            }
+        }
+        dest
+    }
+
+    /// Unpack into a single-channel image, set bits are stored as `T::MAX`
+    pub fn to_image<T: Type>(&self) -> Image<T, Gray> {
+        let mut dest = Image::new(self.size);
+        for (i, x) in dest.data_mut().iter_mut().enumerate() {
+            *x = T::from_norm(if self.get_index(i) { 1.0 } else { 0.0 });
+        }
+        dest
+    }
+
+    /// Image width
+    pub fn width(&self) -> usize {
+        self.size.width
+    }
+
+    /// Image height
+    pub fn height(&self) -> usize {
+        self.size.height
+    }
+
+    /// Image size
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Packed data, bits are stored row-major starting with the least significant bit of the
+    /// first byte
+    pub fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns true when `pt` is in bounds
+    pub fn in_bounds(&self, pt: impl Into<Point>) -> bool {
+        let pt = pt.into();
+        pt.x < self.width() && pt.y < self.height()
+    }
+
+    fn get_index(&self, i: usize) -> bool {
+        self.data[i >> 3] & (1 << (i & 7)) != 0
+    }
+
+    /// Get the bit at `pt`, out of bounds points return `false`
+    pub fn get(&self, pt: impl Into<Point>) -> bool {
+        let pt = pt.into();
+        if !self.in_bounds(pt) {
+            return false;
+        }
+
+        self.get_index(pt.y * self.width() + pt.x)
+    }
+
+    /// Set the bit at `pt`, out of bounds points are ignored
+    pub fn set(&mut self, pt: impl Into<Point>, value: bool) {
+        let pt = pt.into();
+        if !self.in_bounds(pt) {
+            return;
+        }
+
+        let i = pt.y * self.width() + pt.x;
+        if value {
+            self.data[i >> 3] |= 1 << (i & 7);
+        } else {
+            self.data[i >> 3] &= !(1 << (i & 7));
+        }
+    }
+
+    /// Count the number of set bits
+    pub fn count(&self) -> usize {
+        self.data.iter().map(|x| x.count_ones() as usize).sum()
+    }
+}
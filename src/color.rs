@@ -51,7 +51,7 @@ impl Color for Gray {
     }
 
     fn from_rgb(src: &Pixel<Rgb>, mut dest: &mut Pixel<Self>) {
-        dest[0] = src[0] * 0.21 + src[1] * 0.72 + src[2] * 0.7;
+        dest[0] = src[0] * 0.21 + src[1] * 0.72 + src[2] * 0.07;
     }
 }
 
@@ -76,12 +76,12 @@ impl Color for Srgb {
 
     fn to_rgb(rgb: &Pixel<Self>, pixel: &mut Pixel<Rgb>) {
         pixel.copy_from_slice(rgb);
-        pixel.gamma_lin();
+        pixel.to_linear();
     }
 
     fn from_rgb(rgb: &Pixel<Rgb>, pixel: &mut Pixel<Self>) {
         pixel.copy_from_slice(rgb);
-        pixel.gamma_log();
+        pixel.to_srgb();
     }
 }
 
@@ -115,10 +115,11 @@ impl Color for Srgba {
     const ALPHA: Option<Channel> = Some(3);
 
     fn to_rgb(pixel: &Pixel<Self>, mut rgb: &mut Pixel<Rgb>) {
-        rgb[0] = pixel[0] * pixel[3];
-        rgb[1] = pixel[1] * pixel[3];
-        rgb[2] = pixel[2] * pixel[3];
-        rgb.gamma_lin();
+        rgb[0] = pixel[0];
+        rgb[1] = pixel[1];
+        rgb[2] = pixel[2];
+        rgb.to_linear();
+        rgb.map(|x| x * pixel[3]);
     }
 
     fn from_rgb(rgb: &Pixel<Rgb>, mut pixel: &mut Pixel<Self>) {
@@ -126,7 +127,7 @@ impl Color for Srgba {
         pixel[1] = rgb[1];
         pixel[2] = rgb[2];
         pixel[3] = 1.0;
-        pixel.gamma_log();
+        pixel.to_srgb();
     }
 }
 
@@ -195,6 +196,60 @@ impl Color for Xyz {
     }
 }
 
+/// D65 reference white used for Lab conversions
+const D65: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+color!(
+    Lab,
+    "Three-channel CIE-Lab, using the D65 white point. Values are not normalized: L is in the range [0, 100]"
+);
+impl Color for Lab {
+    const NAME: &'static str = "lab";
+    const CHANNELS: Channel = 3;
+
+    fn from_rgb(rgb: &Pixel<Rgb>, mut pixel: &mut Pixel<Self>) {
+        let mut xyz = Pixel::<Xyz>::new();
+        Xyz::from_rgb(rgb, &mut xyz);
+
+        let f = |t: f64| {
+            if t > 0.008856 {
+                t.cbrt()
+            } else {
+                7.787 * t + 16. / 116.
+            }
+        };
+
+        let x = f(xyz[0] / D65[0]);
+        let y = f(xyz[1] / D65[1]);
+        let z = f(xyz[2] / D65[2]);
+
+        pixel[0] = 116. * y - 16.;
+        pixel[1] = 500. * (x - y);
+        pixel[2] = 200. * (y - z);
+    }
+
+    fn to_rgb(px: &Pixel<Self>, rgb: &mut Pixel<Rgb>) {
+        let y = (px[0] + 16.) / 116.;
+        let x = px[1] / 500. + y;
+        let z = y - px[2] / 200.;
+
+        let f = |t: f64| {
+            let t3 = t * t * t;
+            if t3 > 0.008856 {
+                t3
+            } else {
+                (t - 16. / 116.) / 7.787
+            }
+        };
+
+        let mut xyz = Pixel::<Xyz>::new();
+        xyz[0] = f(x) * D65[0];
+        xyz[1] = f(y) * D65[1];
+        xyz[2] = f(z) * D65[2];
+        Xyz::to_rgb(&xyz, rgb);
+    }
+}
+
 color!(Hsv, "Three-channel hue, saturation and value color");
 impl Color for Hsv {
     const NAME: &'static str = "hsv";
@@ -285,6 +340,79 @@ impl Color for Hsv {
     }
 }
 
+color!(Hsl, "Three-channel hue, saturation and lightness color");
+impl Color for Hsl {
+    const NAME: &'static str = "hsl";
+    const CHANNELS: Channel = 3;
+
+    fn from_rgb(rgb: &Pixel<Rgb>, mut pixel: &mut Pixel<Self>) {
+        let r = rgb[0];
+        let g = rgb[1];
+        let b = rgb[2];
+        let cmax = r.max(g).max(b);
+        let cmin = r.min(g).min(b);
+        let delta = cmax - cmin;
+        let l = (cmax + cmin) / 2.;
+
+        if delta == 0. {
+            pixel[0] = 0.;
+            pixel[1] = 0.;
+            pixel[2] = l;
+            return;
+        }
+
+        let s = if l < 0.5 {
+            delta / (cmax + cmin)
+        } else {
+            delta / (2. - cmax - cmin)
+        };
+
+        let h = if cmax == r {
+            (g - b) / delta
+        } else if cmax == g {
+            2. + (b - r) / delta
+        } else {
+            4. + (r - g) / delta
+        };
+
+        pixel[0] = (h / 6.).rem_euclid(1.);
+        pixel[1] = s;
+        pixel[2] = l;
+    }
+
+    fn to_rgb(px: &Pixel<Hsl>, mut rgb: &mut Pixel<Rgb>) {
+        let (h, s, l) = (px[0], px[1], px[2]);
+        if s == 0. {
+            rgb.fill(l);
+            return;
+        }
+
+        let var_2 = if l < 0.5 {
+            l * (1. + s)
+        } else {
+            (l + s) - (s * l)
+        };
+        let var_1 = 2. * l - var_2;
+
+        let hue = |h: f64| {
+            let h = h.rem_euclid(1.);
+            if 6. * h < 1. {
+                var_1 + (var_2 - var_1) * 6. * h
+            } else if 2. * h < 1. {
+                var_2
+            } else if 3. * h < 2. {
+                var_1 + (var_2 - var_1) * ((2. / 3.) - h) * 6.
+            } else {
+                var_1
+            }
+        };
+
+        rgb[0] = hue(h + 1. / 3.);
+        rgb[1] = hue(h);
+        rgb[2] = hue(h - 1. / 3.);
+    }
+}
+
 color!(
     Yuv,
     "Three-channel, luma, blue projection and red projection"
@@ -368,3 +496,29 @@ impl Color for Cmyk {
         rgb[2] = 1.0 - y;
     }
 }
+
+/// N-channel color for multispectral or scientific data, the first three channels are treated as
+/// red, green and blue when converting to other colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Multi<const N: usize>;
+
+unsafe impl<const N: usize> Sync for Multi<N> {}
+unsafe impl<const N: usize> Send for Multi<N> {}
+
+impl<const N: usize> Color for Multi<N> {
+    const NAME: &'static str = "multi";
+    const CHANNELS: Channel = N;
+
+    fn to_rgb(px: &Pixel<Self>, mut rgb: &mut Pixel<Rgb>) {
+        for i in 0..3 {
+            rgb[i] = if i < N { px[i] } else { 0.0 };
+        }
+    }
+
+    fn from_rgb(rgb: &Pixel<Rgb>, mut pixel: &mut Pixel<Self>) {
+        pixel.as_mut().iter_mut().for_each(|x| *x = 0.0);
+        for i in 0..3.min(N) {
+            pixel[i] = rgb[i];
+        }
+    }
+}
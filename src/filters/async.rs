@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use crate::*;
 
 /// AsyncMode is used to schedule the type of iteration for an `AsyncFilter`
@@ -32,6 +35,7 @@ pub struct AsyncPipeline<'a, T: 'a + Type, C: 'a + Color, U: 'a + Type, D: 'a +
     pub(crate) image_schedule_filters: Vec<usize>,
     pub(crate) j: usize,
     pub(crate) index: usize,
+    pub(crate) cancelled: Arc<AtomicBool>,
 }
 
 impl<'a, T: Type, C: Color, U: Unpin + Type, D: Unpin + Color> AsyncPipeline<'a, T, C, U, D> {
@@ -39,6 +43,23 @@ impl<'a, T: Type, C: Color, U: Unpin + Type, D: Unpin + Color> AsyncPipeline<'a,
     pub async fn execute(self) {
         self.await
     }
+
+    /// Stop execution the next time the pipeline is polled, the output image will be left
+    /// partially computed
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst)
+    }
+
+    /// Returns true if the pipeline has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Get a handle that can be used to cancel the pipeline after it has been moved into an
+    /// executor, storing `true` is equivalent to calling `cancel`
+    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
 }
 
 impl<'a, T: Type, C: Color, U: Unpin + Type, D: Unpin + Color> std::future::Future
@@ -51,6 +72,10 @@ impl<'a, T: Type, C: Color, U: Unpin + Type, D: Unpin + Color> std::future::Futu
         ctx: &mut std::task::Context,
     ) -> std::task::Poll<Self::Output> {
         let p = std::pin::Pin::get_mut(self);
+        if p.is_cancelled() {
+            return std::task::Poll::Ready(());
+        }
+
         let pipeline = &p.pipeline;
         let j = p.j;
         let image_schedule_filters = &p.image_schedule_filters;
@@ -92,6 +117,7 @@ pub struct AsyncFilter<
     pub(crate) x: usize,
     pub(crate) y: usize,
     pub(crate) mode: AsyncMode,
+    pub(crate) cancelled: Arc<AtomicBool>,
 }
 
 impl<
@@ -107,6 +133,23 @@ impl<
     pub async fn eval(self) {
         self.await
     }
+
+    /// Stop execution the next time the filter is polled, the output image will be left
+    /// partially computed
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst)
+    }
+
+    /// Returns true if the filter has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Get a handle that can be used to cancel the filter after it has been moved into an
+    /// executor, storing `true` is equivalent to calling `cancel`
+    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
 }
 
 impl<'a, F: Unpin + Filter<T, C, U, D>, T: Type, C: Color, U: Unpin + Type, D: Unpin + Color>
@@ -119,6 +162,10 @@ impl<'a, F: Unpin + Filter<T, C, U, D>, T: Type, C: Color, U: Unpin + Type, D: U
         ctx: &mut std::task::Context,
     ) -> std::task::Poll<Self::Output> {
         let filter = std::pin::Pin::get_mut(self);
+        if filter.cancelled.load(Ordering::SeqCst) {
+            return std::task::Poll::Ready(());
+        }
+
         let width = filter.output.width();
         let height = filter.output.height();
 
@@ -18,6 +18,7 @@ pub trait FilterExt<T: Type, C: Color, U: Type, D: Color>: Sized + Filter<T, C,
             output,
             x: 0,
             y: 0,
+            cancelled: Default::default(),
         }
     }
 
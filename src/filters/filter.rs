@@ -16,6 +16,28 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Convert<D> {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Grayscale([f64; 3]);
+
+/// Convert to grayscale using the given RGB weights, the weights are normalized if they don't
+/// sum to 1
+pub fn grayscale<T: Type, C: Color, U: Type>(weights: [f64; 3]) -> impl Filter<T, C, U, Gray> {
+    let sum: f64 = weights.iter().sum();
+    if sum == 0.0 {
+        return Grayscale(weights);
+    }
+    Grayscale(weights.map(|w| w / sum))
+}
+
+impl<T: Type, C: Color, U: Type> Filter<T, C, U, Gray> for Grayscale {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, Gray>) {
+        let px: Pixel<Rgb> = input.get_pixel(pt, None).convert();
+        let w = &self.0;
+        dest[0] = U::from_norm(px[0] * w[0] + px[1] * w[1] + px[2] * w[2]);
+    }
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Saturation(pub f64);
@@ -34,6 +56,24 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Saturation {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct HueRotate(f64);
+
+/// Rotate hue by the given number of degrees
+pub fn hue_rotate<T: Type, C: Color, U: Type, D: Color>(degrees: f64) -> impl Filter<T, C, U, D> {
+    HueRotate(degrees)
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for HueRotate {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, data: &mut DataMut<U, D>) {
+        let px = input.get_pixel(pt, None);
+        let mut tmp: Pixel<Hsv> = px.convert();
+        tmp[0] = (tmp[0] + self.0 / 360.).rem_euclid(1.0);
+        tmp.convert_to_data(data);
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Brightness(f64);
@@ -85,6 +125,45 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Contrast {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ColorMatrix {
+    matrix: [[f64; 3]; 3],
+    bias: [f64; 3],
+}
+
+/// Apply a 3x3 matrix and bias to the RGB values of each pixel
+pub fn color_matrix<T: Type, C: Color, U: Type, D: Color>(
+    matrix: [[f64; 3]; 3],
+    bias: [f64; 3],
+) -> impl Filter<T, C, U, D> {
+    ColorMatrix { matrix, bias }
+}
+
+/// Sepia tone
+pub fn sepia<T: Type, C: Color, U: Type, D: Color>() -> impl Filter<T, C, U, D> {
+    color_matrix(
+        [
+            [0.393, 0.769, 0.189],
+            [0.349, 0.686, 0.168],
+            [0.272, 0.534, 0.131],
+        ],
+        [0.0; 3],
+    )
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for ColorMatrix {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let px: Pixel<Rgb> = input.get_pixel(pt, None).convert();
+        let mut out = px.clone();
+        for (i, row) in self.matrix.iter().enumerate() {
+            out[i] =
+                (row[0] * px[0] + row[1] * px[1] + row[2] * px[2] + self.bias[i]).clamp(0.0, 1.0);
+        }
+        out.convert_to_data(dest);
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Crop(Region);
@@ -100,13 +179,16 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Crop {
     }
 
     fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
-        if pt.x > self.0.origin.x + self.0.size.width || pt.y > self.0.origin.y + self.0.size.height
-        {
+        if pt.x >= self.0.size.width || pt.y >= self.0.size.height {
             return;
         }
 
         let x = pt.x + self.0.origin.x;
         let y = pt.y + self.0.origin.y;
+        if !input.images()[0].in_bounds((x, y)) {
+            return;
+        }
+
         let px = input.get_pixel((x, y), None);
         px.copy_to_slice(dest);
     }
@@ -129,6 +211,175 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Invert {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ChromaKey {
+    key: [f64; 3],
+    tolerance: f64,
+    softness: f64,
+}
+
+/// Remove pixels close to the `key` color. Pixels within `tolerance` of the key (using Euclidean
+/// distance in normalized RGB) are removed completely and pixels between `tolerance` and
+/// `tolerance + softness` are partially removed. Colors with an alpha channel have their alpha
+/// reduced, other colors are scaled towards zero
+pub fn chroma_key<T: Type, C: Color, U: Type, D: Color>(
+    key: Pixel<Rgb>,
+    tolerance: f64,
+    softness: f64,
+) -> impl Filter<T, C, U, D> {
+    ChromaKey {
+        key: [key[0], key[1], key[2]],
+        tolerance,
+        softness,
+    }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for ChromaKey {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let px = input.get_pixel(pt, None);
+        let rgb: Pixel<Rgb> = px.convert();
+        let d = rgb.distance(&Pixel::from(self.key.as_slice()), DistanceMetric::Euclidean);
+        let amount = if self.softness <= 0.0 {
+            if d > self.tolerance {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            let t = ((d - self.tolerance) / self.softness).clamp(0.0, 1.0);
+            t * t * (3.0 - 2.0 * t)
+        };
+
+        let mut out: Pixel<D> = px.convert();
+        match D::ALPHA {
+            Some(alpha) => out[alpha] *= amount,
+            None => {
+                for c in 0..D::CHANNELS {
+                    out[c] *= amount;
+                }
+            }
+        }
+        out.copy_to_slice(dest);
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Vignette {
+    strength: f64,
+    radius: f64,
+}
+
+/// Darken pixels based on their distance from the center of the image. Distance is normalized
+/// so the corners are at `1.0`, pixels closer than `radius` are unchanged and the corners are
+/// darkened by `strength`.
+///
+/// Note: the center is computed using the size of the first input image, so this filter should
+/// be applied using `run`/`apply` with an output image of the same size
+pub fn vignette<T: Type, C: Color, U: Type, D: Color>(
+    strength: f64,
+    radius: f64,
+) -> impl Filter<T, C, U, D> {
+    Vignette { strength, radius }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Vignette {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let size = input.images()[0].size();
+        let cx = size.width as f64 / 2.0;
+        let cy = size.height as f64 / 2.0;
+        let dx = (pt.x as f64 + 0.5 - cx) / cx;
+        let dy = (pt.y as f64 + 0.5 - cy) / cy;
+        let d = ((dx * dx + dy * dy) / 2.0).sqrt();
+
+        let t = if self.radius >= 1.0 {
+            0.0
+        } else {
+            ((d - self.radius) / (1.0 - self.radius)).clamp(0.0, 1.0)
+        };
+        let amount = 1.0 - self.strength * t * t * (3.0 - 2.0 * t);
+
+        let px = input.get_pixel(pt, None);
+        (px * amount).convert_to_data(dest);
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Posterize(usize);
+
+/// Quantize each channel to the given number of evenly spaced levels
+pub fn posterize<T: Type, C: Color, U: Type, D: Color>(levels: usize) -> impl Filter<T, C, U, D> {
+    Posterize(levels.max(2))
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Posterize {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let n = (self.0 - 1) as f64;
+        let mut px = input.get_pixel(pt, None);
+        px.map(|x| (x.clamp(0.0, 1.0) * n).round() / n);
+        px.convert_to_data(dest);
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct DitherOrdered {
+    size: usize,
+    levels: usize,
+    thresholds: Vec<f64>,
+}
+
+/// Ordered dithering using a Bayer matrix, each channel is quantized to `levels` evenly spaced
+/// values. `matrix_size` is rounded up to the next power of two
+pub fn dither_ordered<T: Type, C: Color, U: Type, D: Color>(
+    matrix_size: usize,
+    levels: usize,
+) -> impl Filter<T, C, U, D> {
+    let size = matrix_size.max(2).next_power_of_two();
+    let mut matrix = vec![0usize];
+    let mut n = 1;
+    while n < size {
+        let mut next = vec![0; 4 * n * n];
+        for y in 0..n {
+            for x in 0..n {
+                let v = 4 * matrix[y * n + x];
+                next[y * 2 * n + x] = v;
+                next[y * 2 * n + x + n] = v + 2;
+                next[(y + n) * 2 * n + x] = v + 3;
+                next[(y + n) * 2 * n + x + n] = v + 1;
+            }
+        }
+        matrix = next;
+        n *= 2;
+    }
+
+    let area = (size * size) as f64;
+    DitherOrdered {
+        size,
+        levels: levels.max(2),
+        thresholds: matrix
+            .into_iter()
+            .map(|v| (v as f64 + 0.5) / area - 0.5)
+            .collect(),
+    }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for DitherOrdered {
+    fn schedule(&self) -> Schedule {
+        Schedule::Pixel
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let n = (self.levels - 1) as f64;
+        let t = self.thresholds[(pt.y % self.size) * self.size + pt.x % self.size];
+        let mut px = input.get_pixel(pt, None);
+        px.map(|x| ((x * n + t).round() / n).clamp(0.0, 1.0));
+        px.convert_to_data(dest);
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Blend;
@@ -146,6 +397,118 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Blend {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct BlendWeighted(f64);
+
+/// Mix two images, `t = 0` selects the first input and `t = 1` selects the second input
+pub fn blend_weighted<T: Type, C: Color, U: Type, D: Color>(t: f64) -> impl Filter<T, C, U, D> {
+    BlendWeighted(t)
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for BlendWeighted {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let a = input.get_pixel(pt, None);
+        let b = input.get_pixel(pt, Some(1));
+        a.lerp(&b, self.0).copy_to_slice(dest);
+    }
+}
+
+/// Blending modes used by `blend_mode`, the first input is the base layer and the second input
+/// is the blend layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// Porter-Duff over, the blend layer is composited on top of the base layer using its alpha
+    Over,
+
+    /// Multiply base and blend values
+    Multiply,
+
+    /// Inverse of multiplying the inverted base and blend values
+    Screen,
+
+    /// Multiply or screen depending on the base value
+    Overlay,
+
+    /// Add base and blend values
+    Add,
+
+    /// Subtract blend value from base value
+    Subtract,
+
+    /// Absolute difference between base and blend values
+    Difference,
+
+    /// Minimum of base and blend values
+    Darken,
+
+    /// Maximum of base and blend values
+    Lighten,
+}
+
+impl BlendMode {
+    /// Blend a single channel value
+    pub fn blend(&self, a: f64, b: f64) -> f64 {
+        match self {
+            BlendMode::Over => b,
+            BlendMode::Multiply => a * b,
+            BlendMode::Screen => 1.0 - (1.0 - a) * (1.0 - b),
+            BlendMode::Overlay => {
+                if a < 0.5 {
+                    2.0 * a * b
+                } else {
+                    1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+                }
+            }
+            BlendMode::Add => a + b,
+            BlendMode::Subtract => a - b,
+            BlendMode::Difference => (a - b).abs(),
+            BlendMode::Darken => a.min(b),
+            BlendMode::Lighten => a.max(b),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct BlendWith(BlendMode);
+
+/// Blend two images using the given `BlendMode`
+pub fn blend_mode<T: Type, C: Color, U: Type, D: Color>(
+    mode: BlendMode,
+) -> impl Filter<T, C, U, D> {
+    BlendWith(mode)
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for BlendWith {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let mut a = input.get_pixel(pt, None);
+        let b = input.get_pixel(pt, Some(1));
+
+        match (self.0, C::ALPHA) {
+            (BlendMode::Over, Some(alpha)) => {
+                let alpha_a = a[alpha];
+                let alpha_b = b[alpha];
+                let out = alpha_b + alpha_a * (1.0 - alpha_b);
+                a.map2(&b, |x, y| {
+                    if out == 0.0 {
+                        0.0
+                    } else {
+                        (y * alpha_b + x * alpha_a * (1.0 - alpha_b)) / out
+                    }
+                });
+                a[alpha] = out;
+            }
+            (mode, _) => {
+                a.map2(&b, |x, y| mode.blend(x, y));
+            }
+        }
+
+        a.copy_to_slice(dest);
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct GammaLog(f64);
@@ -319,6 +682,395 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Normalize {
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Levels {
+    in_black: f64,
+    in_white: f64,
+    gamma: f64,
+    out_black: f64,
+    out_white: f64,
+}
+
+/// Levels adjustment, input values are mapped from `in_black..in_white` to `0..1`, raised to the
+/// power of `gamma` and mapped to `out_black..out_white`. When `in_black == in_white` values at or
+/// above `in_white` map to 1 and all other values map to 0
+pub fn levels<T: Type, C: Color, U: Type, D: Color>(
+    in_black: f64,
+    in_white: f64,
+    gamma: f64,
+    out_black: f64,
+    out_white: f64,
+) -> impl Filter<T, C, U, D> {
+    Levels {
+        in_black,
+        in_white,
+        gamma,
+        out_black,
+        out_white,
+    }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Levels {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        input
+            .get_pixel(pt, None)
+            .map(|x| {
+                let x = if self.in_white == self.in_black {
+                    if x >= self.in_white {
+                        1.
+                    } else {
+                        0.
+                    }
+                } else {
+                    ((x - self.in_black) / (self.in_white - self.in_black)).clamp(0., 1.)
+                };
+                let x = x.powf(self.gamma);
+                (self.out_black + x * (self.out_white - self.out_black)).clamp(0., 1.)
+            })
+            .copy_to_slice(dest)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ChannelMap(Vec<Channel>);
+
+/// Build each output channel from the input channel at the same index in `mapping`, for example
+/// `[2, 1, 0]` will swap the red and blue channels of an `Rgb` image. Returns an error if
+/// `mapping` doesn't have an entry for each output channel or refers to a missing input channel
+pub fn channel_map<T: Type, C: Color, U: Type, D: Color>(
+    mapping: &[Channel],
+) -> Result<impl Filter<T, C, U, D>, Error> {
+    if mapping.len() != D::CHANNELS {
+        return Err(Error::Message(format!(
+            "Channel mapping has {} entries, expected {}",
+            mapping.len(),
+            D::CHANNELS
+        )));
+    }
+
+    if let Some(c) = mapping.iter().find(|c| **c >= C::CHANNELS) {
+        return Err(Error::Message(format!(
+            "Channel {} is out of range for {}",
+            c,
+            C::NAME
+        )));
+    }
+
+    Ok(ChannelMap(mapping.to_vec()))
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for ChannelMap {
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let px = input.get_pixel(pt, None);
+        for (i, c) in self.0.iter().enumerate() {
+            dest[i] = U::from_norm(px[*c]);
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Rank {
+    radius: usize,
+    rank: usize,
+}
+
+/// Rank filter, the values in the `(2 * radius + 1)` square neighborhood of each pixel are
+/// sorted per-channel and the value at index `rank` is selected. A `rank` of 0 selects the
+/// minimum value and any `rank` past the end of the neighborhood selects the maximum value.
+pub fn rank_filter<T: Type, C: Color, U: Type, D: Color>(
+    radius: usize,
+    rank: usize,
+) -> impl Filter<T, C, U, D> {
+    Rank { radius, rank }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Rank {
+    fn schedule(&self) -> Schedule {
+        Schedule::Image
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let image = input.images()[0];
+        let r = self.radius;
+        let mut values = Vec::with_capacity((2 * r + 1) * (2 * r + 1));
+        let mut px = input.new_pixel();
+        for c in 0..C::CHANNELS {
+            values.clear();
+            for y in pt.y.saturating_sub(r)..=pt.y + r {
+                for x in pt.x.saturating_sub(r)..=pt.x + r {
+                    if image.in_bounds((x, y)) {
+                        values.push(image.get((x, y))[c].to_norm());
+                    }
+                }
+            }
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            px[c] = values[self.rank.min(values.len() - 1)];
+        }
+        px.copy_to_slice(dest);
+    }
+}
+
+/// Single box blur pass over normalized, interleaved data using separable running sums, values
+/// outside of the image are treated as zero
+fn box_blur_pass(src: &[f64], size: Size, channels: usize, radius: usize) -> Vec<f64> {
+    let (w, h) = (size.width, size.height);
+    let r = radius as isize;
+    let n = (2 * r + 1) as f64;
+    let index = |x: usize, y: usize, c: usize| (y * w + x) * channels + c;
+
+    // Horizontal pass
+    let mut tmp = vec![0.0; w * h * channels];
+    for y in 0..h {
+        for c in 0..channels {
+            let mut sum: f64 = (0..=r.min(w as isize - 1))
+                .map(|x| src[index(x as usize, y, c)])
+                .sum();
+            for x in 0..w as isize {
+                tmp[index(x as usize, y, c)] = sum;
+                if x + r + 1 < w as isize {
+                    sum += src[index((x + r + 1) as usize, y, c)];
+                }
+                if x - r >= 0 {
+                    sum -= src[index((x - r) as usize, y, c)];
+                }
+            }
+        }
+    }
+
+    // Vertical pass
+    let mut dest = vec![0.0; w * h * channels];
+    for x in 0..w {
+        for c in 0..channels {
+            let mut sum: f64 = (0..=r.min(h as isize - 1))
+                .map(|y| tmp[index(x, y as usize, c)])
+                .sum();
+            for y in 0..h as isize {
+                dest[index(x, y as usize, c)] = sum / (n * n);
+                if y + r + 1 < h as isize {
+                    sum += tmp[index(x, (y + r + 1) as usize, c)];
+                }
+                if y - r >= 0 {
+                    sum -= tmp[index(x, (y - r) as usize, c)];
+                }
+            }
+        }
+    }
+
+    dest
+}
+
+/// Write normalized, interleaved data with the given size into `output`
+fn write_normalized<C: Color, U: Type, D: Color>(
+    data: &[f64],
+    size: Size,
+    output: &mut Image<U, D>,
+) {
+    output.for_each(|pt, mut dest| {
+        if pt.x < size.width && pt.y < size.height {
+            let i = (pt.y * size.width + pt.x) * C::CHANNELS;
+            Pixel::<C>::from(&data[i..i + C::CHANNELS]).copy_to_slice(&mut dest);
+        }
+    });
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct BoxBlur {
+    radius: usize,
+}
+
+/// Box blur using a `(2 * radius + 1)` square neighborhood, pixels outside of the image are
+/// treated as zero. `Filter::eval` uses separable running sums so the cost per pixel doesn't
+/// depend on `radius`, when used in a `Pipeline` each pixel is computed directly
+pub fn box_blur<T: Type, C: Color, U: Type, D: Color>(radius: usize) -> impl Filter<T, C, U, D> {
+    BoxBlur { radius }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for BoxBlur {
+    fn schedule(&self) -> Schedule {
+        Schedule::Image
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let r = self.radius;
+        let n = (2 * r + 1) as f64;
+        let mut px = Pixel::<C>::new();
+        for c in 0..C::CHANNELS {
+            let mut sum = 0.0;
+            for y in pt.y.saturating_sub(r)..=pt.y + r {
+                for x in pt.x.saturating_sub(r)..=pt.x + r {
+                    sum += input.get_f((x, y), c, Some(0));
+                }
+            }
+            px[c] = sum / (n * n);
+        }
+        px.copy_to_slice(dest);
+    }
+
+    fn eval(&self, input: &[&Image<T, C>], output: &mut Image<U, D>) {
+        let image = input[0];
+        let src: Vec<f64> = image.data().iter().map(|x| x.to_norm()).collect();
+        let dest = box_blur_pass(&src, image.size(), C::CHANNELS, self.radius);
+        write_normalized::<C, U, D>(&dest, image.size(), output);
+    }
+
+    fn eval_in_place(&self, image: &mut Image<U, D>) {
+        let input = image.clone();
+        let input = unsafe { &*(&input as *const _ as *const Image<T, C>) };
+        self.eval(&[input], image);
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct FastGaussian {
+    radii: Vec<usize>,
+    weights: Vec<f64>,
+}
+
+/// Approximate gaussian blur using `passes` box blurs with radii chosen to match `sigma`, using
+/// the method described by Peter Kovesi in "Fast Almost-Gaussian Filtering". Three passes gives
+/// a good approximation, pixels outside of the image are treated as zero
+pub fn fast_gaussian<T: Type, C: Color, U: Type, D: Color>(
+    sigma: f64,
+    passes: usize,
+) -> impl Filter<T, C, U, D> {
+    let n = passes.max(1) as f64;
+    let ideal = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut wl = ideal.floor() as isize;
+    if wl % 2 == 0 {
+        wl -= 1;
+    }
+    let wl = wl.max(1) as f64;
+    let wu = wl + 2.0;
+    let m = ((12.0 * sigma * sigma - n * wl * wl - 4.0 * n * wl - 3.0 * n) / (-4.0 * wl - 4.0))
+        .round()
+        .max(0.0) as usize;
+    let radii: Vec<usize> = (0..passes.max(1))
+        .map(|i| {
+            if i < m {
+                wl as usize / 2
+            } else {
+                wu as usize / 2
+            }
+        })
+        .collect();
+
+    // 1-dimensional kernel equivalent to the box passes, used by `compute_at`
+    let mut weights = vec![1.0];
+    for r in &radii {
+        let box_len = 2 * r + 1;
+        let mut next = vec![0.0; weights.len() + box_len - 1];
+        for (i, w) in weights.iter().enumerate() {
+            for x in &mut next[i..i + box_len] {
+                *x += w / box_len as f64;
+            }
+        }
+        weights = next;
+    }
+
+    FastGaussian { radii, weights }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for FastGaussian {
+    fn schedule(&self) -> Schedule {
+        Schedule::Image
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let r = (self.weights.len() / 2) as isize;
+        let mut px = Pixel::<C>::new();
+        for c in 0..C::CHANNELS {
+            let mut sum = 0.0;
+            for (j, wy) in self.weights.iter().enumerate() {
+                let y = pt.y as isize + j as isize - r;
+                if y < 0 {
+                    continue;
+                }
+                for (i, wx) in self.weights.iter().enumerate() {
+                    let x = pt.x as isize + i as isize - r;
+                    if x >= 0 {
+                        sum += wx * wy * input.get_f((x as usize, y as usize), c, Some(0));
+                    }
+                }
+            }
+            px[c] = sum;
+        }
+        px.copy_to_slice(dest);
+    }
+
+    fn eval(&self, input: &[&Image<T, C>], output: &mut Image<U, D>) {
+        let image = input[0];
+        let ch = C::CHANNELS;
+        let (w, h) = (image.width(), image.height());
+
+        // Pad the image so intermediate passes aren't truncated at the edges, this makes the
+        // result match a single convolution with a zero-padded border
+        let pad: usize = self.radii.iter().sum();
+        let size = Size::new(w + 2 * pad, h + 2 * pad);
+        let mut data = vec![0.0; size.width * size.height * ch];
+        let src = image.data();
+        for y in 0..h {
+            for x in 0..w * ch {
+                data[(y + pad) * size.width * ch + pad * ch + x] = src[y * w * ch + x].to_norm();
+            }
+        }
+
+        for r in &self.radii {
+            data = box_blur_pass(&data, size, ch, *r);
+        }
+
+        let mut dest = vec![0.0; w * h * ch];
+        for y in 0..h {
+            let start = (y + pad) * size.width * ch + pad * ch;
+            dest[y * w * ch..(y + 1) * w * ch].copy_from_slice(&data[start..start + w * ch]);
+        }
+        write_normalized::<C, U, D>(&dest, image.size(), output);
+    }
+
+    fn eval_in_place(&self, image: &mut Image<U, D>) {
+        let input = image.clone();
+        let input = unsafe { &*(&input as *const _ as *const Image<T, C>) };
+        self.eval(&[input], image);
+    }
+}
+
+struct Warp<F: Fn(Point) -> (f64, f64)> {
+    f: F,
+}
+
+/// Warp an image using a function that maps each output point to the source coordinates to
+/// sample from, sampling uses `Image::sample` with bilinear interpolation
+pub fn warp<F: Sync + Fn(Point) -> (f64, f64), T: Type, C: Color, U: Type, D: Color>(
+    f: F,
+) -> impl Filter<T, C, U, D> {
+    Warp { f }
+}
+
+impl<F: Fn(Point) -> (f64, f64)> std::fmt::Debug for Warp<F> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Warp").field("f", &"Function").finish()
+    }
+}
+
+impl<F: Sync + Fn(Point) -> (f64, f64), T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D>
+    for Warp<F>
+{
+    fn schedule(&self) -> Schedule {
+        Schedule::Image
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>) {
+        let (x, y) = (self.f)(pt);
+        input.images()[0]
+            .sample(x, y, InterpolationMode::Bilinear)
+            .copy_to_slice(dest);
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Noop;
@@ -402,3 +1154,67 @@ pub fn rotate270<T: Type, C: Color, U: Type, D: Color>(
         Point::new((width / 2.) as usize, (dheight / 2.) as usize),
     )
 }
+
+/// Create a filter from its name and a list of numeric parameters, returns `None` if the name is
+/// unknown or a required parameter is missing.
+///
+/// Supported filters: `noop`, `convert`, `invert`, `clamp`, `sepia`, `brightness(amt)`,
+/// `exposure(stops)`, `contrast(amt)`, `saturation(amt)`, `hue_rotate(degrees)`,
+/// `gamma_log(gamma = 2.2)`, `gamma_lin(gamma = 2.2)`, `posterize(levels)`,
+/// `normalize(min, max, new_min, new_max)`,
+/// `levels(in_black, in_white, gamma = 1, out_black = 0, out_white = 1)`,
+/// `vignette(strength, radius)`, `dither_ordered(matrix_size, levels)`, `box_blur(radius)`,
+/// `fast_gaussian(sigma, passes = 3)`
+pub fn from_spec<T: 'static + Type, C: 'static + Color, U: 'static + Type, D: 'static + Color>(
+    name: &str,
+    params: &[f64],
+) -> Option<Box<dyn Filter<T, C, U, D>>> {
+    let arg = |i: usize| params.get(i).copied();
+    let f: Box<dyn Filter<T, C, U, D>> = match name {
+        "noop" => Box::new(noop()),
+        "convert" => Box::new(convert()),
+        "invert" => Box::new(invert()),
+        "clamp" => Box::new(clamp()),
+        "sepia" => Box::new(sepia()),
+        "brightness" => Box::new(brightness(arg(0)?)),
+        "exposure" => Box::new(exposure(arg(0)?)),
+        "contrast" => Box::new(contrast(arg(0)?)),
+        "saturation" => Box::new(saturation(arg(0)?)),
+        "hue_rotate" => Box::new(hue_rotate(arg(0)?)),
+        "gamma_log" => Box::new(gamma_log(arg(0))),
+        "gamma_lin" => Box::new(gamma_lin(arg(0))),
+        "posterize" => Box::new(posterize(arg(0)? as usize)),
+        "normalize" => Box::new(normalize(arg(0)?, arg(1)?, arg(2)?, arg(3)?)),
+        "levels" => Box::new(levels(
+            arg(0)?,
+            arg(1)?,
+            arg(2).unwrap_or(1.0),
+            arg(3).unwrap_or(0.0),
+            arg(4).unwrap_or(1.0),
+        )),
+        "vignette" => Box::new(vignette(arg(0)?, arg(1)?)),
+        "dither_ordered" => Box::new(dither_ordered(arg(0)? as usize, arg(1)? as usize)),
+        "box_blur" => Box::new(box_blur(arg(0)? as usize)),
+        "fast_gaussian" => Box::new(fast_gaussian(arg(0)?, arg(1).unwrap_or(3.0) as usize)),
+        _ => return None,
+    };
+    Some(f)
+}
+
+/// Parse a filter specification in the form `name` or `name=param,param,...` and create the
+/// filter using `from_spec`
+pub fn parse_spec<T: 'static + Type, C: 'static + Color, U: 'static + Type, D: 'static + Color>(
+    spec: &str,
+) -> Option<Box<dyn Filter<T, C, U, D>>> {
+    let (name, params) = match spec.split_once('=') {
+        Some((name, params)) => {
+            let params = params
+                .split(',')
+                .map(|x| x.trim().parse().ok())
+                .collect::<Option<Vec<f64>>>()?;
+            (name, params)
+        }
+        None => (spec, Vec::new()),
+    };
+    from_spec(name.trim(), &params)
+}
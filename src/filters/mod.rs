@@ -36,6 +36,18 @@ pub trait Filter<T: Type, C: Color, U: Type = T, D: Color = C>: std::fmt::Debug
     /// - `dest`: Single pixel output buffer
     fn compute_at(&self, pt: Point, input: &Input<T, C>, dest: &mut DataMut<U, D>);
 
+    /// Compute filter at the given point for filters that produce more than one output image, by
+    /// default `compute_at` is called for each output
+    ///
+    /// - `pt`: Current output point
+    /// - `input`: Input images
+    /// - `dest`: One pixel output buffer for each output image
+    fn compute_multi_at(&self, pt: Point, input: &Input<T, C>, dest: &mut [DataMut<U, D>]) {
+        for d in dest {
+            self.compute_at(pt, input, d);
+        }
+    }
+
     /// Evaluate a filter on part of an image
     fn eval_partial(&self, roi: Region, input: &[&Image<T, C>], output: &mut Image<U, D>) {
         let input = Input::new(input);
@@ -55,6 +67,22 @@ pub trait Filter<T: Type, C: Color, U: Type = T, D: Color = C>: std::fmt::Debug
         });
     }
 
+    /// Evaluate filter with multiple output images using `compute_multi_at`, only the area
+    /// covered by every output image is computed
+    fn eval_multi(&self, input: &[&Image<T, C>], output: &mut [&mut Image<U, D>]) {
+        let input = Input::new(input);
+        let width = output.iter().map(|x| x.width()).min().unwrap_or(0);
+        let height = output.iter().map(|x| x.height()).min().unwrap_or(0);
+
+        for y in 0..height {
+            for x in 0..width {
+                let pt = Point::new(x, y);
+                let mut data: Vec<_> = output.iter_mut().map(|image| image.get_mut(pt)).collect();
+                self.compute_multi_at(pt, &input, &mut data);
+            }
+        }
+    }
+
     /// Evaluate filter using the same image for input and output, this will
     /// make a copy internally
     fn eval_in_place(&self, image: &mut Image<U, D>) {
@@ -26,6 +26,22 @@ impl<T: Type, C: Color, U: Type, D: Color> Pipeline<T, C, U, D> {
         }
     }
 
+    /// Create a pipeline from a list of filter names and parameters, see `filter::from_spec`.
+    /// Returns `None` if any of the filters can't be created
+    pub fn from_specs(specs: &[(&str, &[f64])]) -> Option<Self>
+    where
+        T: 'static,
+        C: 'static,
+        U: 'static,
+        D: 'static,
+    {
+        let filters = specs
+            .iter()
+            .map(|(name, params)| filter::from_spec(name, params))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Pipeline { filters })
+    }
+
     /// Add a filter to the pipeline
     pub fn push(&mut self, filter: impl 'static + Filter<T, C, U, D>) {
         self.filters.push(Box::new(filter));
@@ -44,7 +60,9 @@ impl<T: Type, C: Color, U: Type, D: Color> Pipeline<T, C, U, D> {
                 dest.push(i);
             }
         }
-        dest.push(self.filters.len() - 1);
+        if dest.last() != Some(&(self.filters.len() - 1)) {
+            dest.push(self.filters.len() - 1);
+        }
         dest
     }
 
@@ -99,6 +117,18 @@ impl<T: Type, C: Color, U: Type, D: Color> Pipeline<T, C, U, D> {
 
     /// Execute the pipeline
     pub fn execute(&self, input: &[&Image<T, C>], output: &mut Image<U, D>) {
+        self.execute_with_progress(input, output, &mut |_| {})
+    }
+
+    /// Execute the pipeline, `progress` is called with the fraction of filters that have been
+    /// completed after each stage. Filters with `Schedule::Pixel` are executed together with the
+    /// next `Schedule::Image` filter, so they are reported at the same time
+    pub fn execute_with_progress(
+        &self,
+        input: &[&Image<T, C>],
+        output: &mut Image<U, D>,
+        progress: &mut dyn FnMut(f64),
+    ) {
         let mut input = Input::new(input);
         let image_schedule_filters = self.image_schedule_list();
 
@@ -113,6 +143,7 @@ impl<T: Type, C: Color, U: Type, D: Color> Pipeline<T, C, U, D> {
                 *index,
                 &image_schedule_filters,
             );
+            progress((index + 1) as f64 / self.filters.len() as f64);
         }
     }
 
@@ -134,6 +165,7 @@ impl<T: Type, C: Color, U: Type, D: Color> Pipeline<T, C, U, D> {
             input,
             output,
             tmpconv: std::cell::UnsafeCell::new(Image::<T, C>::new(size)),
+            cancelled: Default::default(),
         }
     }
 }
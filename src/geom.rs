@@ -6,3 +6,106 @@ pub type Size = euclid::Size2D<usize, f64>;
 
 /// Region of interest
 pub type Region = euclid::Rect<usize, f64>;
+
+/// Region extension methods
+pub trait RegionExt {
+    /// Get the overlapping area of two regions, returns `None` if the regions don't overlap
+    fn intersect(&self, other: &Region) -> Option<Region>;
+
+    /// Get the smallest region containing both regions
+    fn union(&self, other: &Region) -> Region;
+
+    /// Returns true when `other` is completely inside of `self`
+    fn contains_region(&self, other: &Region) -> bool;
+
+    /// Iterate over every point in the region in row-major order
+    fn points(&self) -> impl Iterator<Item = Point>;
+}
+
+impl RegionExt for Region {
+    fn intersect(&self, other: &Region) -> Option<Region> {
+        self.intersection(other)
+    }
+
+    fn union(&self, other: &Region) -> Region {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+        euclid::Rect::union(self, other)
+    }
+
+    fn contains_region(&self, other: &Region) -> bool {
+        self.contains_rect(other)
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> {
+        let (xs, ys) = (self.x_range(), self.y_range());
+        ys.flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+    }
+}
+
+/// Point extension methods
+pub trait PointExt {
+    /// Create a point from floating point coordinates, negative values are clamped to zero
+    fn from_f64(x: f64, y: f64) -> Point;
+
+    /// Add an offset, saturating at `usize::MAX`
+    fn saturating_add(&self, offset: Size) -> Point;
+
+    /// Subtract an offset, saturating at zero
+    fn saturating_sub(&self, offset: Size) -> Point;
+}
+
+impl PointExt for Point {
+    fn from_f64(x: f64, y: f64) -> Point {
+        Point::new(x.max(0.0) as usize, y.max(0.0) as usize)
+    }
+
+    fn saturating_add(&self, offset: Size) -> Point {
+        Point::new(
+            self.x.saturating_add(offset.width),
+            self.y.saturating_add(offset.height),
+        )
+    }
+
+    fn saturating_sub(&self, offset: Size) -> Point {
+        Point::new(
+            self.x.saturating_sub(offset.width),
+            self.y.saturating_sub(offset.height),
+        )
+    }
+}
+
+/// Size extension methods
+pub trait SizeExt {
+    /// Create a size from floating point dimensions, negative values are clamped to zero
+    fn from_f64(width: f64, height: f64) -> Size;
+
+    /// Add two sizes, saturating at `usize::MAX`
+    fn saturating_add(&self, other: Size) -> Size;
+
+    /// Subtract two sizes, saturating at zero
+    fn saturating_sub(&self, other: Size) -> Size;
+}
+
+impl SizeExt for Size {
+    fn from_f64(width: f64, height: f64) -> Size {
+        Size::new(width.max(0.0) as usize, height.max(0.0) as usize)
+    }
+
+    fn saturating_add(&self, other: Size) -> Size {
+        Size::new(
+            self.width.saturating_add(other.width),
+            self.height.saturating_add(other.height),
+        )
+    }
+
+    fn saturating_sub(&self, other: Size) -> Size {
+        Size::new(
+            self.width.saturating_sub(other.width),
+            self.height.saturating_sub(other.height),
+        )
+    }
+}
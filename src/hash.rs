@@ -29,6 +29,90 @@ impl Hash {
     pub fn diff(&self, other: &Hash) -> u32 {
         self.0.distance(&other.0)
     }
+
+    /// Compute hamming distance between two hashes, this is the same as `diff`
+    pub fn hamming_distance(&self, other: &Hash) -> u32 {
+        self.diff(other)
+    }
+
+    /// Create a hash from a 64-bit value, the remaining bits are set to zero
+    pub fn from_u64(bits: u64) -> Hash {
+        let mut data = [0u8; 32];
+        data[..8].copy_from_slice(&bits.to_be_bytes());
+        Hash(blockhash::Blockhash256::from(data))
+    }
+}
+
+fn hash_bits(bits: impl Iterator<Item = bool>) -> Hash {
+    Hash::from_u64(bits.take(64).fold(0u64, |acc, b| (acc << 1) | b as u64))
+}
+
+impl<T: Type, C: Color> Image<T, C> {
+    /// Downscale and convert to brightness values in row-major order
+    fn hash_values(&self, width: usize, height: usize) -> Vec<f64> {
+        let small = self.resize_with((width, height), InterpolationMode::Bilinear);
+        let mut values = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let px = small.get_pixel((x, y));
+                let n = px.iter().count() as f64;
+                values.push(px.iter().sum::<f64>() / n);
+            }
+        }
+        values
+    }
+
+    /// Average hash: each bit of an 8x8 downscaled image is set when the pixel is brighter than
+    /// the mean
+    pub fn ahash(&self) -> Hash {
+        let values = self.hash_values(8, 8);
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        hash_bits(values.into_iter().map(|x| x > mean))
+    }
+
+    /// Difference hash: each bit of a 9x8 downscaled image is set when the pixel is brighter than
+    /// its right neighbor
+    pub fn dhash(&self) -> Hash {
+        let values = self.hash_values(9, 8);
+        hash_bits(
+            values
+                .chunks(9)
+                .flat_map(|row| row.windows(2).map(|w| w[0] > w[1]).collect::<Vec<_>>()),
+        )
+    }
+
+    /// Perceptual hash: each bit of the low-frequency 8x8 block of the DCT of a 32x32 downscaled
+    /// image is set when the coefficient is greater than the median
+    pub fn phash(&self) -> Hash {
+        const N: usize = 32;
+        let values = self.hash_values(N, N);
+
+        let cos: Vec<f64> = (0..8 * N)
+            .map(|i| {
+                let (u, x) = (i / N, i % N);
+                ((2 * x + 1) as f64 * u as f64 * std::f64::consts::PI / (2 * N) as f64).cos()
+            })
+            .collect();
+
+        let mut coeffs = Vec::with_capacity(64);
+        for v in 0..8 {
+            for u in 0..8 {
+                let mut sum = 0.0;
+                for y in 0..N {
+                    for x in 0..N {
+                        sum += values[y * N + x] * cos[u * N + x] * cos[v * N + y];
+                    }
+                }
+                coeffs.push(sum);
+            }
+        }
+
+        // The DC coefficient only depends on overall brightness
+        let mut sorted = coeffs[1..].to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median = sorted[sorted.len() / 2];
+        hash_bits(coeffs.into_iter().map(|x| x > median))
+    }
 }
 
 impl From<Hash> for String {
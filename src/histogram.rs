@@ -128,6 +128,26 @@ impl Histogram {
     pub fn sum(&self) -> usize {
         self.total
     }
+
+    /// Get the index of the bin containing the `p`th percentile (between 0 and 100), a percentile
+    /// of 0 returns the first non-empty bin and 100 returns the last non-empty bin
+    pub fn percentile(&self, p: f64) -> usize {
+        if self.total == 0 {
+            return 0;
+        }
+
+        let target = ((p / 100.0).clamp(0.0, 1.0) * self.total as f64).ceil() as usize;
+        let target = target.max(1);
+        let mut sum = 0;
+        for (i, n) in self.bins() {
+            sum += n;
+            if sum >= target {
+                return i;
+            }
+        }
+
+        self.len() - 1
+    }
 }
 
 #[cfg(test)]
@@ -147,4 +167,18 @@ mod tests {
             assert!(h.distribution().into_iter().skip(1).sum::<f64>() == 0.0);
         }
     }
+
+    #[test]
+    fn test_histogram_percentile() {
+        let mut hist = Histogram::new(10);
+        for i in 2..8 {
+            hist.incr_bin(i);
+            hist.incr_bin(i);
+        }
+
+        assert_eq!(hist.percentile(0.0), 2);
+        assert_eq!(hist.percentile(50.0), 4);
+        assert_eq!(hist.percentile(100.0), 7);
+        assert_eq!(Histogram::new(4).percentile(50.0), 0);
+    }
 }
@@ -3,6 +3,138 @@ use crate::*;
 #[cfg(feature = "parallel")]
 use rayon::{iter::ParallelIterator, prelude::*};
 
+/// Interpolation used by `Image::resize_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterpolationMode {
+    /// Nearest neighbor
+    Nearest,
+
+    /// Bilinear interpolation
+    Bilinear,
+
+    /// Bicubic (Catmull-Rom) interpolation
+    Bicubic,
+}
+
+/// Determines how pixels outside of the image bounds are filled by `Image::pad_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderMode {
+    /// Mirror the image across the edge, the edge pixels are not repeated
+    Reflect,
+
+    /// Repeat the edge pixels
+    Replicate,
+
+    /// Wrap around to the opposite edge
+    Wrap,
+}
+
+impl BorderMode {
+    /// Map a possibly out of bounds index into `0..n`
+    pub fn index(&self, i: isize, n: usize) -> usize {
+        let n = n as isize;
+        match self {
+            BorderMode::Replicate => i.clamp(0, n - 1) as usize,
+            BorderMode::Wrap => i.rem_euclid(n) as usize,
+            BorderMode::Reflect => {
+                if n == 1 {
+                    return 0;
+                }
+                let period = 2 * n - 2;
+                let i = i.rem_euclid(period);
+                (if i >= n { period - i } else { i }) as usize
+            }
+        }
+    }
+}
+
+/// Pixel neighborhood used by `Image::connected_components`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbors
+    Four,
+
+    /// Horizontal, vertical and diagonal neighbors
+    Eight,
+}
+
+/// Source indices and weights used to interpolate at `center` along an axis of length `n`, pixel
+/// centers are located at integer coordinates and indices are clamped to the edges
+fn interpolation_weights(center: f64, n: usize, mode: InterpolationMode) -> Vec<(usize, f64)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let clamp = |i: isize| i.clamp(0, n as isize - 1) as usize;
+    let f = center.floor();
+    let t = center - f;
+    let f = f as isize;
+    match mode {
+        InterpolationMode::Nearest => vec![(clamp((center + 0.5).floor() as isize), 1.0)],
+        InterpolationMode::Bilinear => vec![(clamp(f), 1.0 - t), (clamp(f + 1), t)],
+        InterpolationMode::Bicubic => {
+            let cubic = |x: f64| {
+                let x = x.abs();
+                if x < 1.0 {
+                    1.5 * x * x * x - 2.5 * x * x + 1.0
+                } else if x < 2.0 {
+                    -0.5 * x * x * x + 2.5 * x * x - 4.0 * x + 2.0
+                } else {
+                    0.0
+                }
+            };
+            (-1..=2)
+                .map(|k| (clamp(f + k), cubic(t - k as f64)))
+                .collect()
+        }
+    }
+}
+
+/// Source indices and weights used to compute each destination index along a single axis
+fn resize_weights(src: usize, dst: usize, mode: InterpolationMode) -> Vec<Vec<(usize, f64)>> {
+    let scale = src as f64 / dst as f64;
+
+    (0..dst)
+        .map(|i| {
+            let center = (i as f64 + 0.5) * scale - 0.5;
+            match mode {
+                // Area averaging over the source footprint when downscaling
+                InterpolationMode::Bilinear | InterpolationMode::Bicubic if scale > 1.0 => {
+                    let start = i as f64 * scale;
+                    let end = start + scale;
+                    (start.floor() as usize..(end.ceil() as usize).min(src))
+                        .map(|s| {
+                            let overlap = end.min(s as f64 + 1.0) - start.max(s as f64);
+                            (s, overlap / scale)
+                        })
+                        .filter(|(_, w)| *w > 0.0)
+                        .collect()
+                }
+                _ => interpolation_weights(center, src, mode),
+            }
+        })
+        .collect()
+}
+
+/// Per-channel statistics, all values are normalized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelStats {
+    /// Minimum value
+    pub min: f64,
+
+    /// Maximum value
+    pub max: f64,
+
+    /// Mean value
+    pub mean: f64,
+
+    /// Standard deviation
+    pub stddev: f64,
+}
+
 /// Image type
 pub struct Image<T: Type, C: Color> {
     /// Metadata
@@ -14,7 +146,8 @@ pub struct Image<T: Type, C: Color> {
 
 impl<T: Type, C: Color> PartialEq for Image<T, C> {
     fn eq(&self, other: &Self) -> bool {
-        self.meta == other.meta && self.data.as_ref().as_ref() == other.data.as_ref().as_ref()
+        self.meta.size == other.meta.size
+            && self.data.as_ref().as_ref() == other.data.as_ref().as_ref()
     }
 }
 
@@ -65,6 +198,63 @@ impl<T: Type, C: Color> Image<T, C> {
         })
     }
 
+    /// Create a new image from interleaved pixel data, the length of `data` must be exactly
+    /// `width * height * channels`
+    pub fn from_raw(size: impl Into<Size>, data: Vec<T>) -> Result<Image<T, C>, Error> {
+        let meta = Meta::new(size);
+        if data.len() != meta.num_values() {
+            return Err(Error::InvalidDimensions(
+                meta.width(),
+                meta.height(),
+                C::CHANNELS,
+            ));
+        }
+        Ok(Image {
+            meta,
+            data: Box::new(data),
+        })
+    }
+
+    /// Create a new image from planar (channel-major) pixel data, see `Image::to_planar`
+    pub fn from_planar(size: impl Into<Size>, data: &[T]) -> Result<Image<T, C>, Error> {
+        let mut image = Image::new(size);
+        let n = image.width() * image.height();
+        if data.len() != n * C::CHANNELS {
+            return Err(Error::InvalidDimensions(
+                image.width(),
+                image.height(),
+                C::CHANNELS,
+            ));
+        }
+
+        for (i, px) in image.data_mut().chunks_exact_mut(C::CHANNELS).enumerate() {
+            for (c, x) in px.iter_mut().enumerate() {
+                *x = data[c * n + i];
+            }
+        }
+        Ok(image)
+    }
+
+    /// Create a new image from interleaved pixel data with padded rows, `stride` is the number
+    /// of values between the start of each row. The data is copied into a tightly-packed image
+    pub fn from_raw_with_stride(
+        size: impl Into<Size>,
+        data: &[T],
+        stride: usize,
+    ) -> Result<Image<T, C>, Error> {
+        let mut image = Image::new(size);
+        let row_len = image.width() * C::CHANNELS;
+        let height = image.height();
+        if stride < row_len || (height > 0 && data.len() < stride * (height - 1) + row_len) {
+            return Err(Error::InvalidDimensions(image.width(), height, C::CHANNELS));
+        }
+
+        for (y, row) in image.data_mut().chunks_exact_mut(row_len).enumerate() {
+            row.copy_from_slice(&data[y * stride..y * stride + row_len]);
+        }
+        Ok(image)
+    }
+
     /// Create a new image
     pub fn new(size: impl Into<Size>) -> Image<T, C> {
         let size = size.into();
@@ -75,6 +265,21 @@ impl<T: Type, C: Color> Image<T, C> {
         }
     }
 
+    /// Create a new image by calling `f` with the normalized `(u, v)` coordinates of each pixel,
+    /// both values are in the range `[0, 1]`
+    pub fn generate(
+        size: impl Into<Size>,
+        f: impl Sync + Send + Fn(f64, f64) -> Pixel<C>,
+    ) -> Image<T, C> {
+        let mut image = Image::new(size);
+        let w = (image.width().max(2) - 1) as f64;
+        let h = (image.height().max(2) - 1) as f64;
+        image.for_each(|pt, data| {
+            f(pt.x as f64 / w, pt.y as f64 / h).copy_to_slice(data);
+        });
+        image
+    }
+
     /// Consume image and return inner ImageData
     pub fn into_data(self) -> Box<dyn ImageData<T>> {
         self.data
@@ -82,22 +287,25 @@ impl<T: Type, C: Color> Image<T, C> {
 
     /// Create a new image with the same size, type and color
     pub fn new_like(&self) -> Image<T, C> {
-        Image::new(self.size())
+        self.new_like_with_type_and_color()
     }
 
     /// Create a new image with the same size and color as an existing image with the given type
     pub fn new_like_with_type<U: Type>(&self) -> Image<U, C> {
-        Image::new(self.size())
+        self.new_like_with_type_and_color()
     }
 
     /// Create a new image with the same size and type as an existing image with the given color
     pub fn new_like_with_color<D: Color>(&self) -> Image<T, D> {
-        Image::new(self.size())
+        self.new_like_with_type_and_color()
     }
 
-    /// Create a new image with the same size as an existing image with the given type and color
+    /// Create a new image with the same size as an existing image with the given type and color,
+    /// image attributes are copied to the new image
     pub fn new_like_with_type_and_color<U: Type, D: Color>(&self) -> Image<U, D> {
-        Image::new(self.size())
+        let mut dest = Image::new(self.size());
+        dest.meta.attrs = self.meta.attrs.clone();
+        dest
     }
 
     #[cfg(feature = "mmap")]
@@ -170,8 +378,10 @@ impl<T: Type, C: Color> Image<T, C> {
     /// Update the colorspace associated with an image without performing any conversion
     pub fn with_color<D: Color>(self) -> Image<T, D> {
         assert!(C::CHANNELS == D::CHANNELS);
+        let mut meta = Meta::new(self.meta.size);
+        meta.attrs = self.meta.attrs;
         Image {
-            meta: Meta::new(self.meta.size),
+            meta,
             data: self.data,
         }
     }
@@ -215,6 +425,23 @@ impl<T: Type, C: Color> Image<T, C> {
         pt.x < self.width() && pt.y < self.height()
     }
 
+    /// Get image data from an image, returning `None` when `pt` is out of bounds
+    #[inline]
+    pub fn checked_get(&self, pt: impl Into<Point>) -> Option<Data<'_, T, C>> {
+        let pt = pt.into();
+        if !self.in_bounds(pt) {
+            return None;
+        }
+
+        Some(self.get(pt))
+    }
+
+    /// Get a normalized pixel from an image, returning `None` when `pt` is out of bounds
+    #[inline]
+    pub fn checked_get_pixel(&self, pt: impl Into<Point>) -> Option<Pixel<C>> {
+        self.checked_get(pt).map(|data| data.to_pixel())
+    }
+
     /// Get image data from an image, reusing an existing data buffer big enough for a single pixel
     #[inline]
     pub fn at(&self, pt: impl Into<Point>, mut px: impl AsMut<[T]>) -> bool {
@@ -395,6 +622,32 @@ impl<T: Type, C: Color> Image<T, C> {
         io::read(path)
     }
 
+    /// Read an image from disk along with its attributes (EXIF data, camera information, etc), the
+    /// attributes are also stored in the image `meta`
+    #[cfg(feature = "oiio")]
+    pub fn open_with_meta(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<
+        (
+            Image<T, C>,
+            std::collections::BTreeMap<String, io::oiio::AttrOwned>,
+        ),
+        Error,
+    > {
+        io::read_with_meta(path)
+    }
+
+    /// Read an image from disk and apply the EXIF orientation stored in the file, if any
+    #[cfg(feature = "oiio")]
+    pub fn open_oriented(path: impl AsRef<std::path::Path>) -> Result<Image<T, C>, Error> {
+        let input = io::oiio::ImageInput::open(path, None)?;
+        let image: Image<T, C> = input.read()?;
+        match input.spec().orientation() {
+            Some(o) => Ok(image.orient(o)),
+            None => Ok(image),
+        }
+    }
+
     /// Write an image to disk
     pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), Error> {
         io::write(path, self)
@@ -521,6 +774,135 @@ impl<T: Type, C: Color> Image<T, C> {
         })
     }
 
+    fn color_data(color: &Pixel<C>) -> Vec<T> {
+        let mut data = vec![T::default(); C::CHANNELS];
+        color.copy_to_slice(&mut data);
+        data
+    }
+
+    fn put(&mut self, x: isize, y: isize, data: &[T]) {
+        if x < 0 || y < 0 || x as usize >= self.width() || y as usize >= self.height() {
+            return;
+        }
+        self.get_mut((x as usize, y as usize))
+            .as_mut()
+            .copy_from_slice(data);
+    }
+
+    /// Draw a line from `a` to `b` (inclusive) using Bresenham's algorithm
+    pub fn draw_line(&mut self, a: impl Into<Point>, b: impl Into<Point>, color: &Pixel<C>) {
+        let data = Self::color_data(color);
+        let (a, b) = (a.into(), b.into());
+        let (mut x, mut y) = (a.x as isize, a.y as isize);
+        let (x1, y1) = (b.x as isize, b.y as isize);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.put(x, y, &data);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draw a rectangle, when `filled` is false only the border is drawn
+    pub fn draw_rect(&mut self, roi: Region, color: &Pixel<C>, filled: bool) {
+        if roi.is_empty() {
+            return;
+        }
+
+        let data = Self::color_data(color);
+        let (x0, y0) = (roi.min_x() as isize, roi.min_y() as isize);
+        let (x1, y1) = (roi.max_x() as isize - 1, roi.max_y() as isize - 1);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if filled || x == x0 || x == x1 || y == y0 || y == y1 {
+                    self.put(x, y, &data);
+                }
+            }
+        }
+    }
+
+    /// Draw a circle, when `filled` is false only the outline is drawn
+    pub fn draw_circle(
+        &mut self,
+        center: impl Into<Point>,
+        radius: usize,
+        color: &Pixel<C>,
+        filled: bool,
+    ) {
+        let data = Self::color_data(color);
+        let center = center.into();
+        let (cx, cy) = (center.x as isize, center.y as isize);
+        let r = radius as isize;
+
+        // Midpoint circle algorithm, filled circles draw horizontal spans between octant points
+        let (mut x, mut y) = (r, 0);
+        let mut err = 1 - r;
+        while x >= y {
+            for (dx, dy) in [(x, y), (y, x)] {
+                if filled {
+                    for i in -dx..=dx {
+                        self.put(cx + i, cy + dy, &data);
+                        self.put(cx + i, cy - dy, &data);
+                    }
+                } else {
+                    self.put(cx + dx, cy + dy, &data);
+                    self.put(cx - dx, cy + dy, &data);
+                    self.put(cx + dx, cy - dy, &data);
+                    self.put(cx - dx, cy - dy, &data);
+                }
+            }
+
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Set every pixel to `px`
+    pub fn fill(&mut self, px: &Pixel<C>) {
+        let mut value = vec![T::default(); C::CHANNELS];
+        px.copy_to_slice(&mut value);
+        self.data_mut()
+            .chunks_exact_mut(C::CHANNELS)
+            .for_each(|data| data.copy_from_slice(&value));
+    }
+
+    /// Set every channel to zero, the alpha channel (if any) is set to one
+    pub fn clear(&mut self) {
+        self.fill(&Pixel::new())
+    }
+
+    /// Create a new image by applying `f` to every pixel
+    pub fn map_pixels<U: Type, D: Color>(
+        &self,
+        f: impl Sync + Send + Fn(Point, &Pixel<C>) -> Pixel<D>,
+    ) -> Image<U, D> {
+        let mut dest = Image::new(self.size());
+        dest.for_each(|pt, data| {
+            f(pt, &self.get_pixel(pt)).copy_to_slice(data);
+        });
+        dest
+    }
+
     /// Iterate over a region of pixels qpplying `f` to every pixel
     pub fn for_each_region<F: Sync + Send + Fn(Point, DataMut<T, C>)>(
         &mut self,
@@ -570,6 +952,28 @@ impl<T: Type, C: Color> Image<T, C> {
             });
     }
 
+    /// Add `other` to `self` pixel-wise, clamping each channel between 0 and 1.0
+    pub fn add_saturating(&mut self, other: &Image<T, C>) -> Result<(), Error> {
+        self.check_same_size(other)?;
+        self.for_each2(other, |_, mut a, b| {
+            a.to_pixel()
+                .saturating_add(&b.to_pixel())
+                .copy_to_slice(&mut a);
+        });
+        Ok(())
+    }
+
+    /// Subtract `other` from `self` pixel-wise, clamping each channel between 0 and 1.0
+    pub fn sub_saturating(&mut self, other: &Image<T, C>) -> Result<(), Error> {
+        self.check_same_size(other)?;
+        self.for_each2(other, |_, mut a, b| {
+            a.to_pixel()
+                .saturating_sub(&b.to_pixel())
+                .copy_to_slice(&mut a);
+        });
+        Ok(())
+    }
+
     /// Iterate over pixels, with a mutable closure
     pub fn each_pixel<F: Sync + Send + FnMut(Point, &Pixel<C>)>(&self, mut f: F) {
         let meta = self.meta();
@@ -653,13 +1057,347 @@ impl<T: Type, C: Color> Image<T, C> {
             })
     }
 
-    /// Copy a region of an image to a new image
+    /// Copy a single channel into a new grayscale image
+    pub fn extract_channel(&self, c: Channel) -> Result<Image<T, Gray>, Error> {
+        if c >= C::CHANNELS {
+            return Err(Error::Message(format!("Invalid channel: {c}")));
+        }
+
+        let mut dest = self.new_like_with_color::<Gray>();
+        dest.for_each(|pt, mut px| {
+            px[0] = self.get(pt)[c];
+        });
+        Ok(dest)
+    }
+
+    /// Replace each pixel with the per-channel minimum of its `(2 * radius + 1)` square
+    /// neighborhood
+    pub fn min_filter(&self, radius: usize) -> Image<T, C> {
+        self.run(filter::rank_filter(radius, 0), None)
+    }
+
+    /// Replace each pixel with the per-channel maximum of its `(2 * radius + 1)` square
+    /// neighborhood
+    pub fn max_filter(&self, radius: usize) -> Image<T, C> {
+        self.run(filter::rank_filter(radius, usize::MAX), None)
+    }
+
+    /// Get a read-only view of a region of the image without copying, `roi` is clamped to the
+    /// image bounds
+    pub fn view(&self, roi: Region) -> ImageView<'_, T, C> {
+        ImageView::new(self, roi)
+    }
+
+    /// Add a border around an image filled with `fill`
+    pub fn pad(
+        &self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        fill: &Pixel<C>,
+    ) -> Image<T, C> {
+        let size = Size::new(self.width() + left + right, self.height() + top + bottom);
+        let (w, h) = (self.width(), self.height());
+        let mut dest = Image::new(size);
+        dest.for_each(|pt, mut data| {
+            if pt.x >= left && pt.x < left + w && pt.y >= top && pt.y < top + h {
+                data.copy_from_slice(self.get((pt.x - left, pt.y - top)));
+            } else {
+                fill.copy_to_slice(data);
+            }
+        });
+        dest
+    }
+
+    /// Add a border around an image, the border is filled using pixels from the image
+    /// according to `mode`. Padding an empty image returns an image filled with zeros
+    pub fn pad_with(
+        &self,
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        mode: BorderMode,
+    ) -> Image<T, C> {
+        let size = Size::new(self.width() + left + right, self.height() + top + bottom);
+        let (w, h) = (self.width(), self.height());
+        if w == 0 || h == 0 {
+            return Image::new(size);
+        }
+
+        self.remap(size, |pt| {
+            Point::new(
+                mode.index(pt.x as isize - left as isize, w),
+                mode.index(pt.y as isize - top as isize, h),
+            )
+        })
+    }
+
+    /// Stack images from left to right, all images must have the same height
+    pub fn hconcat(images: &[&Image<T, C>]) -> Result<Image<T, C>, Error> {
+        let first = match images.first() {
+            Some(first) => first,
+            None => return Err(Error::Message("no images to concatenate".to_string())),
+        };
+
+        if let Some(image) = images.iter().find(|i| i.height() != first.height()) {
+            return Err(Error::InvalidDimensions(
+                image.width(),
+                image.height(),
+                C::CHANNELS,
+            ));
+        }
+
+        let width = images.iter().map(|i| i.width()).sum::<usize>();
+        let mut dest = Image::new((width, first.height()));
+        let mut x = 0;
+        for image in images {
+            let n = image.width() * C::CHANNELS;
+            for y in 0..image.height() {
+                dest.row_mut(y).as_slice_mut()[x..x + n].copy_from_slice(image.row(y).as_slice());
+            }
+            x += n;
+        }
+        Ok(dest)
+    }
+
+    /// Stack images from top to bottom, all images must have the same width
+    pub fn vconcat(images: &[&Image<T, C>]) -> Result<Image<T, C>, Error> {
+        let first = match images.first() {
+            Some(first) => first,
+            None => return Err(Error::Message("no images to concatenate".to_string())),
+        };
+
+        if let Some(image) = images.iter().find(|i| i.width() != first.width()) {
+            return Err(Error::InvalidDimensions(
+                image.width(),
+                image.height(),
+                C::CHANNELS,
+            ));
+        }
+
+        let height = images.iter().map(|i| i.height()).sum::<usize>();
+        let mut dest = Image::new((first.width(), height));
+        let mut offs = 0;
+        for image in images {
+            let n = image.data().len();
+            dest.data_mut()[offs..offs + n].copy_from_slice(image.data());
+            offs += n;
+        }
+        Ok(dest)
+    }
+
+    /// Split an image into tiles of the given size, returning each tile along with its origin.
+    /// Tiles along the right and bottom edges may be smaller than `tile`
+    pub fn split_tiles(&self, tile: impl Into<Size>) -> Vec<(Point, Image<T, C>)> {
+        let tile = tile.into();
+        assert!(tile.width > 0 && tile.height > 0);
+
+        let mut tiles = Vec::new();
+        for y in (0..self.height()).step_by(tile.height) {
+            for x in (0..self.width()).step_by(tile.width) {
+                let origin = Point::new(x, y);
+                let size = Size::new(
+                    tile.width.min(self.width() - x),
+                    tile.height.min(self.height() - y),
+                );
+                tiles.push((origin, self.view(Region::new(origin, size)).to_image()));
+            }
+        }
+        tiles
+    }
+
+    /// Assemble an image of the given size from tiles and their origins, any part of a tile that
+    /// falls outside of the image is ignored
+    pub fn from_tiles(size: impl Into<Size>, tiles: &[(Point, Image<T, C>)]) -> Image<T, C> {
+        let mut dest = Image::new(size);
+        for (origin, tile) in tiles {
+            if origin.x >= dest.width() {
+                continue;
+            }
+
+            let width = tile.width().min(dest.width() - origin.x);
+            let start = origin.x * C::CHANNELS;
+            let end = start + width * C::CHANNELS;
+            for y in 0..tile.height().min(dest.height().saturating_sub(origin.y)) {
+                dest.row_mut(origin.y + y).as_slice_mut()[start..end]
+                    .copy_from_slice(&tile.row(y).as_slice()[..width * C::CHANNELS]);
+            }
+        }
+        dest
+    }
+
+    /// Get the smallest region containing every pixel that differs from `background` by more
+    /// than `tolerance` in any channel, returns `None` when no pixels differ
+    pub fn bounding_box(&self, background: &Pixel<C>, tolerance: f64) -> Option<Region> {
+        let mut min = Point::new(usize::MAX, usize::MAX);
+        let mut max = Point::new(0, 0);
+        let mut px = self.new_pixel();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.pixel_at((x, y), &mut px);
+                if (0..C::CHANNELS).any(|c| (px[c] - background[c]).abs() > tolerance) {
+                    min.x = min.x.min(x);
+                    min.y = min.y.min(y);
+                    max.x = max.x.max(x);
+                    max.y = max.y.max(y);
+                }
+            }
+        }
+
+        if min.x > max.x {
+            return None;
+        }
+
+        Some(Region::new(
+            min,
+            Size::new(max.x - min.x + 1, max.y - min.y + 1),
+        ))
+    }
+
+    /// Mix two images, `t = 0` returns a copy of `self` and `t = 1` returns a copy of `other`
+    pub fn blend_weighted(&self, other: &Image<T, C>, t: f64) -> Image<T, C> {
+        let mut dest = self.new_like();
+        dest.apply(filter::blend_weighted(t), &[self, other]);
+        dest
+    }
+
+    fn luminance(&self) -> Image<f64, Gray> {
+        self.convert()
+    }
+
+    fn check_same_size<U: Type, D: Color>(&self, other: &Image<U, D>) -> Result<(), Error> {
+        if self.size() != other.size() {
+            return Err(Error::InvalidDimensions(
+                other.width(),
+                other.height(),
+                D::CHANNELS,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the absolute difference in luminance between two images of the same size
+    pub fn diff(&self, other: &Image<T, C>) -> Result<Image<f64, Gray>, Error> {
+        self.check_same_size(other)?;
+        let b = other.luminance();
+        let mut dest = self.luminance();
+        dest.for_each(|pt, mut px| {
+            px[0] = (px[0] - b.get(pt)[0]).abs();
+        });
+        Ok(dest)
+    }
+
+    /// Structural similarity between two images of the same size, computed on luminance using
+    /// 8x8 windows with a step of 4 pixels. Returns a value in `-1..=1`, where 1 means the images
+    /// are identical
+    pub fn ssim(&self, other: &Image<T, C>) -> Result<f64, Error> {
+        self.check_same_size(other)?;
+        const C1: f64 = 0.01 * 0.01;
+        const C2: f64 = 0.03 * 0.03;
+
+        let a = self.luminance();
+        let b = other.luminance();
+        let (w, h) = (self.width(), self.height());
+        if w == 0 || h == 0 {
+            return Ok(1.0);
+        }
+
+        let window = 8;
+        let (ww, wh) = (window.min(w), window.min(h));
+        let mut total = 0.0;
+        let mut count = 0;
+        let mut y = 0;
+        while y + wh <= h {
+            let mut x = 0;
+            while x + ww <= w {
+                let n = (ww * wh) as f64;
+                let (mut ma, mut mb) = (0.0, 0.0);
+                for j in y..y + wh {
+                    for i in x..x + ww {
+                        ma += a.get((i, j))[0];
+                        mb += b.get((i, j))[0];
+                    }
+                }
+                ma /= n;
+                mb /= n;
+
+                let (mut va, mut vb, mut cov) = (0.0, 0.0, 0.0);
+                for j in y..y + wh {
+                    for i in x..x + ww {
+                        let da = a.get((i, j))[0] - ma;
+                        let db = b.get((i, j))[0] - mb;
+                        va += da * da;
+                        vb += db * db;
+                        cov += da * db;
+                    }
+                }
+                va /= n;
+                vb /= n;
+                cov /= n;
+
+                total += ((2.0 * ma * mb + C1) * (2.0 * cov + C2))
+                    / ((ma * ma + mb * mb + C1) * (va + vb + C2));
+                count += 1;
+                x += 4;
+            }
+            y += 4;
+        }
+
+        Ok(total / count as f64)
+    }
+
+    /// Mean squared error between the normalized values of two images of the same size
+    pub fn mse(&self, other: &Image<T, C>) -> Result<f64, Error> {
+        self.check_same_size(other)?;
+        let n = self.meta.num_values();
+        if n == 0 {
+            return Ok(0.0);
+        }
+
+        let sum: f64 = self.data()[..n]
+            .iter()
+            .zip(&other.data()[..n])
+            .map(|(a, b)| (a.to_norm() - b.to_norm()).powi(2))
+            .sum();
+        Ok(sum / n as f64)
+    }
+
+    /// Peak signal-to-noise ratio in decibels between two images of the same size, using
+    /// normalized values with a peak of 1. Identical images return `f64::INFINITY`, images with
+    /// different sizes return an error
+    pub fn psnr(&self, other: &Image<T, C>) -> Result<f64, Error> {
+        let mse = self.mse(other)?;
+        Ok(-10.0 * mse.log10())
+    }
+
+    /// Copy a region of an image to a new image, parts of `roi` outside of the source image are
+    /// left as zero
     pub fn crop(&self, roi: Region) -> Image<T, C> {
         let mut dest = Image::new(roi.size);
         dest.apply(filter::crop(roi), &[self]);
         dest
     }
 
+    /// Copy a region of an image to a new image, returns an error if `roi` is empty or not
+    /// completely inside of the source image
+    pub fn try_crop(&self, roi: Region) -> Result<Image<T, C>, Error> {
+        if roi.is_empty() {
+            return Err(Error::InvalidDimensions(
+                roi.width(),
+                roi.height(),
+                C::CHANNELS,
+            ));
+        }
+
+        if !Region::from_size(self.size()).contains_region(&roi) {
+            return Err(Error::OutOfBounds(roi.max_x(), roi.max_y()));
+        }
+
+        Ok(self.crop(roi))
+    }
+
     /// Copy into a region from another image starting at the given offset
     pub fn copy_from_region(&mut self, offs: impl Into<Point>, other: &Image<T, C>, roi: Region) {
         let offs = offs.into();
@@ -680,6 +1418,16 @@ impl<T: Type, C: Color> Image<T, C> {
         self
     }
 
+    /// Apply a dynamic filter using an Image as output
+    pub fn apply_boxed<U: Type, D: Color>(
+        &mut self,
+        filter: &dyn Filter<U, D, T, C>,
+        input: &[&Image<U, D>],
+    ) -> &mut Self {
+        filter.eval(input, self);
+        self
+    }
+
     /// Apply an async filter using an Image as output
     pub async fn apply_async<'a, U: Type, D: Color>(
         &mut self,
@@ -691,6 +1439,23 @@ impl<T: Type, C: Color> Image<T, C> {
         self
     }
 
+    /// Apply a filter using an Image as output on a tokio runtime, yielding to the scheduler after
+    /// each row so other tasks are able to make progress
+    #[cfg(feature = "tokio")]
+    pub async fn apply_tokio<U: Type, D: Color>(
+        &mut self,
+        filter: impl Filter<U, D, T, C>,
+        input: &[&Image<U, D>],
+    ) -> &mut Self {
+        let width = self.width();
+        for y in 0..self.height() {
+            let roi = Region::new(Point::new(0, y), Size::new(width, 1));
+            filter.eval_partial(roi, input, self);
+            tokio::task::yield_now().await;
+        }
+        self
+    }
+
     /// Run a filter using the same Image as input and output
     pub fn run_in_place(&mut self, filter: impl Filter<T, C>) -> &mut Self {
         filter.eval_in_place(self);
@@ -703,16 +1468,36 @@ impl<T: Type, C: Color> Image<T, C> {
         filter: impl Filter<T, C, U, D>,
         output: Option<Meta<U, D>>,
     ) -> Image<U, D> {
-        let size = if let Some(o) = output {
-            o.size
-        } else {
-            self.size()
-        };
-        let mut dest = Image::new(size);
+        let mut dest = self.output_image(&filter, output);
         dest.apply(filter, &[self]);
         dest
     }
 
+    /// Create the destination image for `run`, when `output` is `None` the size is determined by
+    /// `Filter::output_size`. Attributes are copied from `self`, those set in `output` take
+    /// precedence
+    fn output_image<U: Type, D: Color>(
+        &self,
+        filter: &impl Filter<T, C, U, D>,
+        output: Option<Meta<U, D>>,
+    ) -> Image<U, D> {
+        if let Some(o) = output {
+            let mut dest = Image::new(o.size);
+            dest.meta.attrs = self.meta.attrs.clone();
+            dest.meta.attrs.extend(o.attrs);
+            return dest;
+        }
+
+        let mut dest = self.new_like_with_type_and_color();
+        let input = [self];
+        let size = filter.output_size(&Input::new(&input), &mut dest);
+        if size != dest.size() {
+            dest = Image::new(size);
+            dest.meta.attrs = self.meta.attrs.clone();
+        }
+        dest
+    }
+
     /// Run an async filter using an Image as input
     pub async fn run_async<'a, U: 'a + Type, D: 'a + Color>(
         &self,
@@ -720,19 +1505,50 @@ impl<T: Type, C: Color> Image<T, C> {
         filter: impl Filter<T, C, U, D> + Unpin,
         output: Option<Meta<U, D>>,
     ) -> Image<U, D> {
-        let size = if let Some(o) = output {
-            o.size
-        } else {
-            self.size()
-        };
-        let mut dest = Image::new(size);
+        let mut dest = self.output_image(&filter, output);
         dest.apply_async(mode, filter, &[self]).await;
         dest
     }
 
     /// Convert image type/color
+    ///
+    /// This produces the same output as running `filter::convert`, but works a row at a time
+    /// reusing the intermediate pixels instead of allocating them for every pixel. When the
+    /// `parallel` feature is enabled rows are converted in parallel.
     pub fn convert<U: Type, D: Color>(&self) -> Image<U, D> {
-        self.run(filter::convert(), None)
+        let mut dest = self.new_like_with_type_and_color();
+        dest.rows_mut().for_each(|(y, row)| {
+            let mut src = Pixel::<C>::new();
+            let mut rgb = Pixel::<Rgb>::new();
+            let mut px = Pixel::<D>::new();
+            let input = self.row(y);
+            input
+                .as_ref()
+                .chunks_exact(C::CHANNELS)
+                .zip(row.chunks_exact_mut(D::CHANNELS))
+                .for_each(|(a, b)| {
+                    src.copy_from_slice(a);
+                    C::to_rgb(&src, &mut rgb);
+                    D::from_rgb(&rgb, &mut px);
+                    px.copy_to_slice(b);
+                });
+        });
+        dest
+    }
+
+    /// Convert to an 8-bit RGB image suitable for display, floating point images are assumed to
+    /// be linear and are gamma encoded before being clamped
+    pub fn to_display(&self) -> Image<u8, Rgb> {
+        let mut dest = self.new_like_with_type_and_color::<u8, Rgb>();
+        let float = T::is_float();
+        dest.for_each(|pt, data| {
+            let mut px: Pixel<Rgb> = self.get_pixel(pt).convert();
+            if float {
+                px.clamp().gamma_log();
+            }
+            px.clamp().copy_to_slice(data);
+        });
+        dest
     }
 
     /// Convert image type/color
@@ -794,6 +1610,225 @@ impl<T: Type, C: Color> Image<T, C> {
         Ok(dest)
     }
 
+    /// Get per-pixel CIE76 color difference between two images of the same size
+    pub fn delta_e_map(&self, other: &Image<T, C>) -> Result<Image<f32, Gray>, Error> {
+        self.check_same_size(other)?;
+        let mut dest = self.new_like_with_type_and_color::<f32, Gray>();
+        dest.for_each(|pt, mut px| {
+            let a: Pixel<Lab> = self.get_pixel(pt).convert();
+            let b: Pixel<Lab> = other.get_pixel(pt).convert();
+            px[0] = a.delta_e(&b) as f32;
+        });
+        Ok(dest)
+    }
+
+    /// Find the `k` most dominant colors using k-means clustering in `Lab` space, returning each
+    /// cluster center along with the fraction of pixels assigned to it. Large images are
+    /// subsampled to at most 65536 pixels
+    pub fn dominant_colors(&self, k: usize, iterations: usize) -> Vec<(Pixel<C>, f64)> {
+        const MAX_SAMPLES: usize = 1 << 16;
+        let step = (self.width() * self.height()).div_ceil(MAX_SAMPLES).max(1);
+        let mut pixel = Pixel::<C>::new();
+        let pixels: Vec<Pixel<Lab>> = self
+            .data()
+            .chunks_exact(C::CHANNELS)
+            .step_by(step)
+            .map(|px| {
+                pixel.copy_from_slice(px);
+                pixel.convert()
+            })
+            .collect();
+
+        if pixels.is_empty() || k == 0 {
+            return Vec::new();
+        }
+
+        // Farthest-point initialization keeps the result deterministic
+        let mut centers = vec![pixels[0].clone()];
+        while centers.len() < k.min(pixels.len()) {
+            let next = pixels
+                .iter()
+                .map(|px| {
+                    centers
+                        .iter()
+                        .map(|c| px.delta_e(c))
+                        .fold(f64::INFINITY, f64::min)
+                })
+                .enumerate()
+                .fold((0, -1.0), |a, b| if b.1 > a.1 { b } else { a })
+                .0;
+            centers.push(pixels[next].clone());
+        }
+
+        let nearest = |centers: &[Pixel<Lab>], px: &Pixel<Lab>| {
+            centers
+                .iter()
+                .map(|c| px.delta_e(c))
+                .enumerate()
+                .fold((0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a })
+                .0
+        };
+
+        let mut counts = vec![0usize; centers.len()];
+        for _ in 0..iterations.max(1) {
+            let mut sums = vec![Pixel::<Lab>::new(); centers.len()];
+            counts.iter_mut().for_each(|c| *c = 0);
+            for px in &pixels {
+                let i = nearest(&centers, px);
+                for c in 0..Lab::CHANNELS {
+                    sums[i][c] += px[c];
+                }
+                counts[i] += 1;
+            }
+
+            let mut changed = false;
+            for (i, sum) in sums.into_iter().enumerate() {
+                if counts[i] == 0 {
+                    continue;
+                }
+
+                let mut center = sum;
+                for c in 0..Lab::CHANNELS {
+                    center[c] /= counts[i] as f64;
+                }
+
+                if center != centers[i] {
+                    changed = true;
+                }
+                centers[i] = center;
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let total = pixels.len() as f64;
+        centers
+            .iter()
+            .zip(counts)
+            .map(|(c, n)| (c.convert(), n as f64 / total))
+            .collect()
+    }
+
+    /// Combine every pixel into a single value, pixels are visited in row-major order
+    pub fn reduce<A>(&self, init: A, f: impl Fn(A, Point, &Pixel<C>) -> A) -> A {
+        let mut acc = init;
+        let mut px = Pixel::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pt = Point::new(x, y);
+                px.copy_from_slice(self.get(pt));
+                acc = f(acc, pt, &px);
+            }
+        }
+        acc
+    }
+
+    /// Parallel version of `Image::reduce`, each row is folded starting from `init()` and the
+    /// results are merged using `combine`, which should be associative
+    #[cfg(feature = "parallel")]
+    pub fn par_reduce<A: Send>(
+        &self,
+        init: impl Sync + Send + Fn() -> A,
+        f: impl Sync + Send + Fn(A, Point, &Pixel<C>) -> A,
+        combine: impl Sync + Send + Fn(A, A) -> A,
+    ) -> A {
+        (0..self.height())
+            .into_par_iter()
+            .map(|y| {
+                let mut acc = init();
+                let mut px = Pixel::new();
+                for x in 0..self.width() {
+                    let pt = Point::new(x, y);
+                    px.copy_from_slice(self.get(pt));
+                    acc = f(acc, pt, &px);
+                }
+                acc
+            })
+            .reduce(&init, &combine)
+    }
+
+    /// Replace each pixel with the nearest palette entry, using Euclidean distance. If the palette
+    /// is empty the image is copied unchanged
+    pub fn quantize_to_palette(&self, palette: &[Pixel<C>]) -> Image<T, C> {
+        if palette.is_empty() {
+            return self.clone();
+        }
+
+        self.map_pixels(|_, px| {
+            let i = palette
+                .iter()
+                .map(|p| px.distance(p, DistanceMetric::Euclidean))
+                .enumerate()
+                .fold((0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a })
+                .0;
+            palette[i].clone()
+        })
+    }
+
+    /// Get per-channel min, max, mean and standard deviation in a single pass
+    pub fn statistics(&self) -> Vec<ChannelStats> {
+        let mut min = vec![f64::INFINITY; C::CHANNELS];
+        let mut max = vec![f64::NEG_INFINITY; C::CHANNELS];
+        let mut mean = vec![0.0; C::CHANNELS];
+        let mut m2 = vec![0.0; C::CHANNELS];
+        let mut n = 0.0;
+
+        self.each_pixel(|_, px| {
+            n += 1.0;
+            for c in 0..C::CHANNELS {
+                let x = px[c];
+                min[c] = min[c].min(x);
+                max[c] = max[c].max(x);
+                let delta = x - mean[c];
+                mean[c] += delta / n;
+                m2[c] += delta * (x - mean[c]);
+            }
+        });
+
+        (0..C::CHANNELS)
+            .map(|c| {
+                if n == 0.0 {
+                    return ChannelStats {
+                        min: 0.0,
+                        max: 0.0,
+                        mean: 0.0,
+                        stddev: 0.0,
+                    };
+                }
+                ChannelStats {
+                    min: min[c],
+                    max: max[c],
+                    mean: mean[c],
+                    stddev: (m2[c] / n).sqrt(),
+                }
+            })
+            .collect()
+    }
+
+    /// Compute the summed-area table of an image, the result is one pixel wider and taller than
+    /// the source image with the first row and column set to zero. Each value contains the sum of
+    /// the normalized values above and to the left of it
+    pub fn integral(&self) -> Image<f64, C> {
+        let (w, h) = (self.width(), self.height());
+        let mut dest = Image::<f64, C>::new((w + 1, h + 1));
+        let stride = (w + 1) * C::CHANNELS;
+        let data = dest.data_mut();
+        for y in 0..h {
+            let row = self.row(y);
+            let mut sum = vec![0.0; C::CHANNELS];
+            for x in 0..w {
+                for c in 0..C::CHANNELS {
+                    sum[c] += row[x * C::CHANNELS + c].to_norm();
+                    let i = (y + 1) * stride + (x + 1) * C::CHANNELS + c;
+                    data[i] = data[i - stride] + sum[c];
+                }
+            }
+        }
+        dest
+    }
+
     /// Get image histogram
     pub fn histogram(&self, bins: usize) -> Vec<Histogram> {
         let mut hist = vec![Histogram::new(bins); C::CHANNELS];
@@ -807,6 +1842,54 @@ impl<T: Type, C: Color> Image<T, C> {
         hist
     }
 
+    /// Get image histogram after applying `gamma` to each value, using a gamma of `1.0 / 2.2`
+    /// on linear images gives a perceptual histogram similar to the one displayed by image editors
+    pub fn histogram_gamma(&self, bins: usize, gamma: f64) -> Vec<Histogram> {
+        let mut hist = vec![Histogram::new(bins); C::CHANNELS];
+
+        self.each_pixel(|_, px| {
+            for i in 0..C::CHANNELS {
+                hist[i].add_value(px[i].clamp(0.0, 1.0).powf(gamma));
+            }
+        });
+
+        hist
+    }
+
+    /// Stretch each color channel so values between the `low_percentile` and `high_percentile`
+    /// (between 0 and 100) of the channel histogram span the full range, values outside that range
+    /// are clipped. Channels without a meaningful range and the alpha channel are left unchanged
+    pub fn auto_contrast(&mut self, low_percentile: f64, high_percentile: f64) {
+        const BINS: usize = 1024;
+        let n = (BINS - 1) as f64;
+        let bounds: Vec<Option<(f64, f64)>> = self
+            .histogram(BINS)
+            .iter()
+            .enumerate()
+            .map(|(c, hist)| {
+                if C::ALPHA == Some(c) {
+                    return None;
+                }
+                // Use the inner edges of the percentile bins so every value in those bins is
+                // clipped
+                let low = (hist.percentile(low_percentile) as f64 + 0.5) / n;
+                let high = (hist.percentile(high_percentile) as f64 - 0.5) / n;
+                if high <= low {
+                    return None;
+                }
+                Some((low, high))
+            })
+            .collect();
+
+        self.each_pixel_mut(|_, mut px| {
+            for (c, b) in bounds.iter().enumerate() {
+                if let Some((low, high)) = b {
+                    px[c] = ((px[c] - low) / (high - low)).clamp(0.0, 1.0);
+                }
+            }
+        });
+    }
+
     /// Gamma correction
     pub fn gamma(&mut self, value: f64) {
         self.for_each(|_, px| {
@@ -816,6 +1899,86 @@ impl<T: Type, C: Color> Image<T, C> {
         })
     }
 
+    /// Multiply color channels by alpha, this is a no-op for colors without an alpha channel
+    pub fn premultiply_alpha(&mut self) {
+        if let Some(alpha) = C::ALPHA {
+            self.each_pixel_mut(|_, px| {
+                let a = px[alpha];
+                px.map(|x| x * a);
+            })
+        }
+    }
+
+    /// Divide color channels by alpha, this is a no-op for colors without an alpha channel
+    pub fn unpremultiply_alpha(&mut self) {
+        if let Some(alpha) = C::ALPHA {
+            self.each_pixel_mut(|_, px| {
+                let a = px[alpha];
+                if a != 0.0 {
+                    px.map(|x| x / a);
+                }
+            })
+        }
+    }
+
+    /// Set pixels to zero where `mask` is zero, or where `mask` is non-zero when `invert` is true
+    pub fn apply_mask(&mut self, mask: &Image<u8, Gray>, invert: bool) -> Result<(), Error> {
+        if mask.size() != self.size() {
+            return Err(Error::InvalidDimensions(
+                mask.width(),
+                mask.height(),
+                Gray::CHANNELS,
+            ));
+        }
+
+        self.for_each(|pt, px| {
+            if (mask.get(pt)[0] == 0) != invert {
+                for x in px {
+                    *x = T::from_f64(0.0);
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Floyd-Steinberg error diffusion dithering, each color channel is quantized to `levels`
+    /// evenly spaced values. The alpha channel is not modified
+    pub fn dither_floyd_steinberg(&mut self, levels: usize) {
+        let n = (levels.max(2) - 1) as f64;
+        let (w, h) = (self.width(), self.height());
+        let mut values: Vec<f64> = self.data().iter().map(|x| x.to_norm()).collect();
+
+        for c in (0..C::CHANNELS).filter(|c| Some(*c) != C::ALPHA) {
+            let index = |x: usize, y: usize| (y * w + x) * C::CHANNELS + c;
+            for y in 0..h {
+                for x in 0..w {
+                    let i = index(x, y);
+                    let old = values[i];
+                    let new = (old.clamp(0.0, 1.0) * n).round() / n;
+                    values[i] = new;
+                    let err = old - new;
+
+                    if x + 1 < w {
+                        values[index(x + 1, y)] += err * 7.0 / 16.0;
+                    }
+                    if y + 1 < h {
+                        if x > 0 {
+                            values[index(x - 1, y + 1)] += err * 3.0 / 16.0;
+                        }
+                        values[index(x, y + 1)] += err * 5.0 / 16.0;
+                        if x + 1 < w {
+                            values[index(x + 1, y + 1)] += err / 16.0;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (d, v) in self.data_mut().iter_mut().zip(values) {
+            *d = T::from_norm(v);
+        }
+    }
+
     /// Convert to log RGB
     pub fn set_gamma_log(&mut self) {
         self.gamma(1. / 2.2)
@@ -826,12 +1989,285 @@ impl<T: Type, C: Color> Image<T, C> {
         self.gamma(2.2)
     }
 
+    /// Encode linear values using the piecewise sRGB transfer function, unlike `set_gamma_log`
+    /// this includes the linear segment near zero. The alpha channel is not modified
+    pub fn to_srgb(&mut self) {
+        self.each_pixel_mut(|_, px| {
+            px.to_srgb();
+        })
+    }
+
+    /// Decode sRGB encoded values to linear using the piecewise sRGB transfer function, the
+    /// inverse of `to_srgb`. The alpha channel is not modified
+    pub fn to_linear(&mut self) {
+        self.each_pixel_mut(|_, px| {
+            px.to_linear();
+        })
+    }
+
+    /// Bilinear interpolation at `(x, y)`, neighboring pixels that are out of bounds are replaced
+    /// with `fill`
+    fn bilinear(&self, x: f64, y: f64, fill: &Pixel<C>) -> Pixel<C> {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+
+        let get = |x: f64, y: f64| {
+            if x < 0. || y < 0. || !self.in_bounds((x as usize, y as usize)) {
+                fill.clone()
+            } else {
+                self.get_pixel((x as usize, y as usize))
+            }
+        };
+
+        let top = get(x0, y0) * (1. - fx) + &(get(x0 + 1., y0) * fx);
+        let bottom = get(x0, y0 + 1.) * (1. - fx) + &(get(x0 + 1., y0 + 1.) * fx);
+        top * (1. - fy) + &(bottom * fy)
+    }
+
+    /// Sample the image at a fractional position, pixel centers are located at integer
+    /// coordinates and positions outside of the image are clamped to the nearest edge
+    pub fn sample(&self, x: f64, y: f64, interp: InterpolationMode) -> Pixel<C> {
+        let mut px = Pixel::<C>::from(vec![0.0; C::CHANNELS]);
+        if self.width() == 0 || self.height() == 0 {
+            return px;
+        }
+
+        for (j, wy) in interpolation_weights(y, self.height(), interp) {
+            for (i, wx) in interpolation_weights(x, self.width(), interp) {
+                let src = self.get((i, j));
+                let w = wx * wy;
+                for c in 0..C::CHANNELS {
+                    px[c] += src[c].to_norm() * w;
+                }
+            }
+        }
+        px
+    }
+
+    /// Rotate an image by `degrees` about `pivot` using bilinear sampling, the pivot stays in the
+    /// same place in the output image and any pixels outside of the source image are set to
+    /// `fill`
+    pub fn rotate_about(
+        &self,
+        degrees: f64,
+        pivot: impl Into<Point>,
+        fill: Pixel<C>,
+    ) -> Image<T, C> {
+        let pivot = pivot.into();
+        let (px, py) = (pivot.x as f64, pivot.y as f64);
+        let (sin, cos) = (-degrees).to_radians().sin_cos();
+        let mut dest = self.new_like();
+        dest.for_each(|pt, data| {
+            let dx = pt.x as f64 - px;
+            let dy = pt.y as f64 - py;
+            let x = px + dx * cos - dy * sin;
+            let y = py + dx * sin + dy * cos;
+            self.bilinear(x, y, &fill).copy_to_slice(data);
+        });
+        dest
+    }
+
+    /// Create a new image of the given size where each pixel is copied from the source pixel
+    /// returned by `f`
+    fn remap(&self, size: Size, f: impl Sync + Send + Fn(Point) -> Point) -> Image<T, C> {
+        let mut dest = Image::new(size);
+        dest.for_each(|pt, mut data| data.copy_from_slice(self.get(f(pt))));
+        dest
+    }
+
+    /// Mirror an image horizontally
+    pub fn flip_horizontal(&self) -> Image<T, C> {
+        let w = self.width();
+        self.remap(self.size(), |pt| Point::new(w - 1 - pt.x, pt.y))
+    }
+
+    /// Mirror an image vertically
+    pub fn flip_vertical(&self) -> Image<T, C> {
+        let h = self.height();
+        self.remap(self.size(), |pt| Point::new(pt.x, h - 1 - pt.y))
+    }
+
+    /// Swap the x and y axes of an image
+    pub fn transpose(&self) -> Image<T, C> {
+        let size = Size::new(self.height(), self.width());
+        self.remap(size, |pt| Point::new(pt.y, pt.x))
+    }
+
+    /// Rotate an image 90 degrees clockwise without resampling
+    pub fn rotate90(&self) -> Image<T, C> {
+        let h = self.height();
+        let size = Size::new(h, self.width());
+        self.remap(size, |pt| Point::new(pt.y, h - 1 - pt.x))
+    }
+
+    /// Rotate an image 180 degrees without resampling
+    pub fn rotate180(&self) -> Image<T, C> {
+        let (w, h) = (self.width(), self.height());
+        self.remap(self.size(), |pt| Point::new(w - 1 - pt.x, h - 1 - pt.y))
+    }
+
+    /// Rotate an image 270 degrees clockwise without resampling
+    pub fn rotate270(&self) -> Image<T, C> {
+        let w = self.width();
+        let size = Size::new(self.height(), w);
+        self.remap(size, |pt| Point::new(w - 1 - pt.y, pt.x))
+    }
+
+    /// Apply an EXIF orientation (1-8) so the image is displayed upright, unknown values return
+    /// an unmodified copy
+    pub fn orient(&self, orientation: i32) -> Image<T, C> {
+        match orientation {
+            2 => self.flip_horizontal(),
+            3 => self.rotate180(),
+            4 => self.flip_vertical(),
+            5 => self.transpose(),
+            6 => self.rotate90(),
+            7 => self.rotate270().flip_horizontal(),
+            8 => self.rotate270(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Resize an image using the given interpolation mode, when shrinking an axis using
+    /// `Bilinear` or `Bicubic` the source pixels covered by each destination pixel are averaged
+    pub fn resize_with(&self, size: impl Into<Size>, mode: InterpolationMode) -> Image<T, C> {
+        let size = size.into();
+        if self.width() == 0 || self.height() == 0 {
+            return Image::new(size);
+        }
+
+        let xw = resize_weights(self.width(), size.width, mode);
+        let yw = resize_weights(self.height(), size.height, mode);
+        let mut dest = Image::new(size);
+        dest.for_each(|pt, data| {
+            let mut px = Pixel::<C>::from(vec![0.0; C::CHANNELS]);
+            for (y, wy) in &yw[pt.y] {
+                for (x, wx) in &xw[pt.x] {
+                    let src = self.get((*x, *y));
+                    let w = wx * wy;
+                    for c in 0..C::CHANNELS {
+                        px[c] += src[c].to_norm() * w;
+                    }
+                }
+            }
+            px.copy_to_slice(data);
+        });
+        dest
+    }
+
     /// Resize an image
     pub fn resize(&self, size: impl Into<Size>) -> Image<T, C> {
         let size = size.into();
         self.run(filter::resize(self.size(), size), Some(Meta::new(size)))
     }
 
+    /// Downscale an image by half in each dimension, each output pixel is the mean of the
+    /// corresponding 2x2 block of input pixels. Averaging is performed using normalized values and
+    /// rounded when converting back to integer types to avoid darkening the image.
+    pub fn pyramid_mean_downscale(&self) -> Image<T, C> {
+        let (w, h) = (self.width(), self.height());
+        if w == 0 || h == 0 {
+            return Image::new((w / 2, h / 2));
+        }
+
+        let width = (w / 2).max(1);
+        let height = (h / 2).max(1);
+        let mut dest = Image::new((width, height));
+        let float = T::is_float();
+        dest.for_each(|pt, mut data| {
+            let mut sum = vec![0.0; C::CHANNELS];
+            let mut n = 0.0;
+            for y in pt.y * 2..pt.y * 2 + 2 {
+                for x in pt.x * 2..pt.x * 2 + 2 {
+                    if !self.in_bounds((x, y)) {
+                        continue;
+                    }
+
+                    for (s, v) in sum.iter_mut().zip(self.get((x, y))) {
+                        *s += v.to_norm();
+                    }
+                    n += 1.0;
+                }
+            }
+
+            for (d, s) in data.as_mut().iter_mut().zip(sum) {
+                let x = T::denormalize(s / n);
+                *d = T::from_f64(if float { x } else { x.round() });
+            }
+        });
+        dest
+    }
+
+    /// Downscale an image by half in each dimension using a 2x2 box average, see
+    /// `Image::pyramid_mean_downscale`
+    pub fn downsample_2x(&self) -> Image<T, C> {
+        self.pyramid_mean_downscale()
+    }
+
+    /// Upscale an image to twice the size in each dimension using bilinear interpolation, pixel
+    /// centers are aligned with the source image and edges are clamped
+    pub fn upsample_2x(&self) -> Image<T, C> {
+        let (w, h) = (self.width(), self.height());
+        if w == 0 || h == 0 {
+            return Image::new((w * 2, h * 2));
+        }
+
+        let mut dest = Image::new((w * 2, h * 2));
+        let float = T::is_float();
+
+        // Each output pixel is 1/4 of a source pixel away from its nearest source pixel center
+        let taps = |i: usize, n: usize| {
+            let s = i / 2;
+            if i & 1 == 0 {
+                [(s.saturating_sub(1), 0.25), (s, 0.75)]
+            } else {
+                [(s, 0.75), ((s + 1).min(n - 1), 0.25)]
+            }
+        };
+
+        dest.for_each(|pt, mut data| {
+            let mut sum = vec![0.0; C::CHANNELS];
+            for (y, wy) in taps(pt.y, h) {
+                for (x, wx) in taps(pt.x, w) {
+                    for (s, v) in sum.iter_mut().zip(self.get((x, y))) {
+                        *s += v.to_norm() * wx * wy;
+                    }
+                }
+            }
+
+            for (d, s) in data.as_mut().iter_mut().zip(sum) {
+                let x = T::denormalize(s);
+                *d = T::from_f64(if float { x } else { x.round() });
+            }
+        });
+        dest
+    }
+
+    /// Generate a mipmap chain starting with a copy of the original image, each level is half the
+    /// size of the previous level (rounded down) until a 1x1 image is reached
+    pub fn generate_mipmaps(&self) -> Vec<Image<T, C>> {
+        let mut levels = vec![self.clone()];
+        loop {
+            let last = &levels[levels.len() - 1];
+            if last.width() <= 1 && last.height() <= 1 {
+                break;
+            }
+            let next = last.downsample_2x();
+            levels.push(next);
+        }
+        levels
+    }
+
+    /// Scale an image by an integer `factor` using nearest-neighbor sampling, each source pixel
+    /// is copied into a `factor x factor` block
+    pub fn scale_nearest(&self, factor: usize) -> Image<T, C> {
+        assert!(factor > 0);
+        let size = Size::new(self.width() * factor, self.height() * factor);
+        self.remap(size, |pt| Point::new(pt.x / factor, pt.y / factor))
+    }
+
     /// Scale an image
     pub fn scale(&self, width: f64, height: f64) -> Image<T, C> {
         self.run(
@@ -852,4 +2288,181 @@ impl<T: Type, C: Color> Image<T, C> {
     pub fn data_mut(&mut self) -> &mut [T] {
         self.data.data_mut()
     }
+
+    /// Copy image data into a new `Vec` using planar (channel-major) layout: all values for the
+    /// first channel, followed by all values for the second channel, etc...
+    pub fn to_planar(&self) -> Vec<T> {
+        let n = self.width() * self.height();
+        let data = self.data();
+        let mut dest = Vec::with_capacity(n * C::CHANNELS);
+        for c in 0..C::CHANNELS {
+            dest.extend((0..n).map(|i| data[i * C::CHANNELS + c]));
+        }
+        dest
+    }
+
+    /// Copy image data into a new, interleaved `Vec`
+    pub fn to_vec(&self) -> Vec<T> {
+        self.data()[..self.meta.num_values()].to_vec()
+    }
+}
+
+impl<C: Color> Image<f64, C> {
+    /// Get the sum of the values inside `roi` using a summed-area table created by
+    /// `Image::integral`
+    pub fn integral_sum(&self, roi: Region) -> Pixel<C> {
+        let (x0, y0) = (roi.min_x(), roi.min_y());
+        let (x1, y1) = (roi.max_x(), roi.max_y());
+        let (a, b, c, d) = (
+            self.get((x0, y0)),
+            self.get((x1, y0)),
+            self.get((x0, y1)),
+            self.get((x1, y1)),
+        );
+        let mut px = Pixel::new();
+        for i in 0..C::CHANNELS {
+            px[i] = d[i] - b[i] - c[i] + a[i];
+        }
+        px
+    }
+}
+
+impl<T: Type> Image<T, Rgba> {
+    /// Composite the image over a gray and white checkerboard with squares of the given size,
+    /// producing an opaque preview of the image
+    pub fn on_checkerboard(&self, square: usize) -> Image<T, Rgb> {
+        let square = square.max(1);
+        let mut dest = self.new_like_with_color::<Rgb>();
+        dest.for_each(|pt, data| {
+            let bg = if (pt.x / square + pt.y / square) & 1 == 0 {
+                1.0
+            } else {
+                0.8
+            };
+            let px = self.get_pixel(pt);
+            let alpha = px[3];
+            let mut out = Pixel::<Rgb>::new();
+            for c in 0..3 {
+                out[c] = px[c] * alpha + bg * (1.0 - alpha);
+            }
+            out.copy_to_slice(data);
+        });
+        dest
+    }
+}
+
+impl<T: Type> Image<T, Gray> {
+    /// Label connected regions of pixels with normalized values greater than `threshold`,
+    /// returning the label image and the number of components. Background pixels are labeled
+    /// `0` and components are labeled from `1` in scanline order
+    pub fn connected_components(
+        &self,
+        threshold: f64,
+        connectivity: Connectivity,
+    ) -> (Image<u32, Gray>, usize) {
+        fn find(parent: &mut [u32], mut x: u32) -> u32 {
+            while parent[x as usize] != x {
+                parent[x as usize] = parent[parent[x as usize] as usize];
+                x = parent[x as usize];
+            }
+            x
+        }
+
+        let (w, h) = (self.width(), self.height());
+        let mut labels: Image<u32, Gray> = Image::new((w, h));
+        let mut parent = vec![0u32];
+
+        let neighbors: &[(isize, isize)] = match connectivity {
+            Connectivity::Four => &[(-1, 0), (0, -1)],
+            Connectivity::Eight => &[(-1, 0), (-1, -1), (0, -1), (1, -1)],
+        };
+
+        // First pass: assign provisional labels and record equivalences
+        for y in 0..h {
+            for x in 0..w {
+                if self.get((x, y))[0].to_norm() <= threshold {
+                    continue;
+                }
+
+                let mut label = 0;
+                for (dx, dy) in neighbors {
+                    let (nx, ny) = (x as isize + dx, y as isize + dy);
+                    if nx < 0 || ny < 0 || nx >= w as isize {
+                        continue;
+                    }
+
+                    let n = labels.get((nx as usize, ny as usize))[0];
+                    if n == 0 {
+                        continue;
+                    }
+
+                    if label == 0 {
+                        label = n;
+                    } else {
+                        let (a, b) = (find(&mut parent, label), find(&mut parent, n));
+                        parent[a.max(b) as usize] = a.min(b);
+                    }
+                }
+
+                if label == 0 {
+                    label = parent.len() as u32;
+                    parent.push(label);
+                }
+                labels.get_mut((x, y))[0] = label;
+            }
+        }
+
+        // Second pass: resolve equivalences and make labels sequential
+        let mut remap = vec![0u32; parent.len()];
+        let mut count = 0;
+        for i in 1..parent.len() {
+            let root = find(&mut parent, i as u32) as usize;
+            if remap[root] == 0 {
+                count += 1;
+                remap[root] = count;
+            }
+            remap[i] = remap[root];
+        }
+
+        labels
+            .data_mut()
+            .iter_mut()
+            .for_each(|l| *l = remap[*l as usize]);
+        (labels, count as usize)
+    }
+}
+
+impl Image<u32, Gray> {
+    /// Get the bounding box of each label in a labeled image, label `0` is treated as background
+    /// and skipped
+    pub fn bounding_boxes(&self) -> std::collections::HashMap<u32, Region> {
+        let mut extents: std::collections::HashMap<u32, (Point, Point)> =
+            std::collections::HashMap::new();
+
+        for y in 0..self.height() {
+            for (x, label) in self.row(y).as_slice().iter().enumerate() {
+                if *label == 0 {
+                    continue;
+                }
+
+                let e = extents
+                    .entry(*label)
+                    .or_insert((Point::new(x, y), Point::new(x, y)));
+                e.0.x = e.0.x.min(x);
+                e.0.y = e.0.y.min(y);
+                e.1.x = e.1.x.max(x);
+                e.1.y = e.1.y.max(y);
+            }
+        }
+
+        extents
+            .into_iter()
+            .map(|(label, (min, max))| {
+                (
+                    label,
+                    Region::new(min, Size::new(max.x - min.x + 1, max.y - min.y + 1)),
+                )
+            })
+            .collect()
+    }
 }
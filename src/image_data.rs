@@ -88,6 +88,21 @@ pub mod mmap {
     use memmap2::MmapOptions;
     use std::io::{Read, Write};
 
+    /// Magic bytes at the beginning of every memory-mapped image file, the last four bytes
+    /// contain the header version
+    pub const MAGIC: &[u8; 8] = b"img2\0\0\0\x02";
+
+    /// Number of bytes reserved for the color name in the header, this also pads the header to 64
+    /// bytes so the pixel data is aligned
+    const COLOR_NAME_LEN: usize = 30;
+
+    fn color_name<C: Color>() -> [u8; COLOR_NAME_LEN] {
+        let mut name = [0u8; COLOR_NAME_LEN];
+        let n = C::NAME.len().min(COLOR_NAME_LEN);
+        name[..n].copy_from_slice(&C::NAME.as_bytes()[..n]);
+        name
+    }
+
     /// Memory-mapped image data
     pub struct Mmap<T: Type> {
         inner: memmap2::MmapMut,
@@ -96,10 +111,12 @@ pub mod mmap {
 
     impl<T: Type> Mmap<T> {
         fn header_len() -> u64 {
-            4 + std::mem::size_of::<u64>() as u64
+            MAGIC.len() as u64
+                + std::mem::size_of::<u64>() as u64
                 + std::mem::size_of::<u64>() as u64
                 + std::mem::size_of::<u64>() as u64
                 + std::mem::size_of::<u16>() as u64
+                + COLOR_NAME_LEN as u64
         }
 
         /// Write header to file
@@ -107,11 +124,12 @@ pub mod mmap {
             mut file: impl Write,
             meta: &Meta<T, C>,
         ) -> Result<(), Error> {
-            file.write_all(b"img2")?;
+            file.write_all(MAGIC)?;
             file.write_all(&(std::mem::size_of::<T>() as u64).to_le_bytes())?;
             file.write_all(&(meta.width() as u64).to_le_bytes())?;
             file.write_all(&(meta.height() as u64).to_le_bytes())?;
             file.write_all(&(C::CHANNELS as u16).to_le_bytes())?;
+            file.write_all(&color_name::<C>())?;
             Ok(())
         }
 
@@ -155,10 +173,15 @@ pub mod mmap {
 
         /// Read header from file on disk
         pub fn read_header<C: Color>(mut file: impl Read) -> Result<Meta<T, C>, Error> {
-            let mut hdr = [0u8; 4];
+            let mut hdr = [0u8; 8];
             file.read_exact(&mut hdr)?;
 
-            if hdr.as_slice() != b"img2" {
+            if &hdr != MAGIC {
+                if &hdr[..4] == b"img2" {
+                    return Err(Error::Message(
+                        "unsupported mmap header version".to_string(),
+                    ));
+                }
                 return Err(Error::Message("invalid mmap header".to_string()));
             }
 
@@ -182,6 +205,13 @@ pub mod mmap {
                 return Err(Error::InvalidType);
             }
 
+            let mut name = [0u8; COLOR_NAME_LEN];
+            file.read_exact(&mut name)?;
+
+            if name != color_name::<C>() {
+                return Err(Error::InvalidType);
+            }
+
             let width = u64::from_le_bytes(width) as usize;
             let height = u64::from_le_bytes(height) as usize;
 
@@ -212,6 +242,31 @@ pub mod mmap {
             Ok((data, meta))
         }
 
+        /// Load read-only `MmapReadOnly` from disk, this only requires read access to the file
+        pub fn load_readonly<C: Color>(
+            filename: impl AsRef<std::path::Path>,
+        ) -> Result<(MmapReadOnly<T>, Meta<T, C>), Error> {
+            let mut file = std::fs::OpenOptions::new().read(true).open(filename)?;
+
+            let meta = Self::read_header(&mut file)?;
+
+            let inner = unsafe { MmapOptions::new().offset(Self::header_len()).map(&file)? };
+
+            let data = MmapReadOnly {
+                inner,
+                _t: std::marker::PhantomData,
+            };
+            Ok((data, meta))
+        }
+
+        /// Load read-only image from disk
+        pub fn load_image_readonly<C: Color>(
+            filename: impl AsRef<std::path::Path>,
+        ) -> Result<ReadOnlyImage<T, C>, Error> {
+            let (data, meta) = Self::load_readonly::<C>(filename)?;
+            ReadOnlyImage::new(meta, data)
+        }
+
         /// Load image from disk
         pub fn load_image<C: Color>(
             filename: impl AsRef<std::path::Path>,
@@ -265,6 +320,96 @@ pub mod mmap {
             let _ = self.flush();
         }
     }
+
+    /// Read-only memory-mapped image data
+    pub struct MmapReadOnly<T: Type> {
+        inner: memmap2::Mmap,
+        _t: std::marker::PhantomData<T>,
+    }
+
+    impl<T: Type> AsRef<[T]> for MmapReadOnly<T> {
+        fn as_ref(&self) -> &[T] {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.inner.as_ptr() as *const _,
+                    self.inner.len() / std::mem::size_of::<T>(),
+                )
+            }
+        }
+    }
+
+    /// Read-only memory-mapped image, unlike `Image` there are no methods that modify the
+    /// underlying data. Use `to_image` to get a mutable copy
+    pub struct ReadOnlyImage<T: Type, C: Color> {
+        meta: Meta<T, C>,
+        data: MmapReadOnly<T>,
+    }
+
+    impl<T: Type, C: Color> ReadOnlyImage<T, C> {
+        fn new(meta: Meta<T, C>, data: MmapReadOnly<T>) -> Result<Self, Error> {
+            if data.as_ref().len() < meta.num_values() {
+                return Err(Error::InvalidDimensions(
+                    meta.width(),
+                    meta.height(),
+                    C::CHANNELS,
+                ));
+            }
+            Ok(ReadOnlyImage { meta, data })
+        }
+
+        /// Get image meta
+        pub fn meta(&self) -> &Meta<T, C> {
+            &self.meta
+        }
+
+        /// Image width
+        pub fn width(&self) -> usize {
+            self.meta.width()
+        }
+
+        /// Image height
+        pub fn height(&self) -> usize {
+            self.meta.height()
+        }
+
+        /// Image size
+        pub fn size(&self) -> Size {
+            self.meta.size()
+        }
+
+        /// Image data
+        pub fn data(&self) -> &[T] {
+            &self.data.as_ref()[..self.meta.num_values()]
+        }
+
+        /// Returns true when `pt` is in bounds
+        pub fn in_bounds(&self, pt: impl Into<Point>) -> bool {
+            let pt = pt.into();
+            pt.x < self.width() && pt.y < self.height()
+        }
+
+        /// Get image data at `pt`, returning `None` when `pt` is out of bounds
+        pub fn get(&self, pt: impl Into<Point>) -> Option<Data<'_, T, C>> {
+            let pt = pt.into();
+            if !self.in_bounds(pt) {
+                return None;
+            }
+            let index = self.meta.index(pt);
+            Some(Data::new(&self.data()[index..index + C::CHANNELS]))
+        }
+
+        /// Get a normalized pixel, returning `None` when `pt` is out of bounds
+        pub fn get_pixel(&self, pt: impl Into<Point>) -> Option<Pixel<C>> {
+            self.get(pt).map(|data| data.to_pixel())
+        }
+
+        /// Copy into a new, mutable `Image`
+        pub fn to_image(&self) -> Image<T, C> {
+            let mut dest = Image::new(self.size());
+            dest.data_mut().copy_from_slice(self.data());
+            dest
+        }
+    }
 }
 
 impl<const N: usize, T: Type> ImageData<T> for [T; N] {
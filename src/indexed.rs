@@ -0,0 +1,109 @@
+use crate::*;
+
+/// An image that stores palette indices instead of colors
+pub struct IndexedImage {
+    indices: Image<u8, Gray>,
+    palette: Vec<Pixel<Rgb>>,
+}
+
+impl IndexedImage {
+    /// Create a new indexed image, returns an error if the palette has more than 256 entries or
+    /// any index is out of range
+    pub fn new(indices: Image<u8, Gray>, palette: Vec<Pixel<Rgb>>) -> Result<IndexedImage, Error> {
+        if palette.len() > 256 {
+            return Err(Error::Message(format!(
+                "Palette has {} entries, the maximum is 256",
+                palette.len()
+            )));
+        }
+
+        if let Some(n) = indices
+            .data()
+            .iter()
+            .position(|i| *i as usize >= palette.len())
+        {
+            return Err(Error::OutOfBounds(n % indices.width(), n / indices.width()));
+        }
+
+        Ok(IndexedImage { indices, palette })
+    }
+
+    /// Build an indexed image with a palette of `k` colors (at most 256) using
+    /// `Image::dominant_colors`, each pixel is assigned to the nearest palette entry. Returns an
+    /// error if `k` is zero
+    pub fn from_rgb_quantized<T: Type>(
+        image: &Image<T, Rgb>,
+        k: usize,
+    ) -> Result<IndexedImage, Error> {
+        if k == 0 {
+            return Err(Error::Message(
+                "Palette must have at least one entry".to_string(),
+            ));
+        }
+
+        let palette: Vec<Pixel<Rgb>> = image
+            .dominant_colors(k.min(256), 10)
+            .into_iter()
+            .map(|(px, _)| px)
+            .collect();
+
+        let mut indices = Image::new(image.size());
+        indices.for_each(|pt, mut data| {
+            let px = image.get_pixel(pt);
+            data[0] = palette
+                .iter()
+                .map(|p| px.distance(p, DistanceMetric::Euclidean))
+                .enumerate()
+                .fold((0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a })
+                .0 as u8;
+        });
+
+        IndexedImage::new(indices, palette)
+    }
+
+    /// Palette index for each pixel
+    pub fn indices(&self) -> &Image<u8, Gray> {
+        &self.indices
+    }
+
+    /// Color palette, at most 256 entries
+    pub fn palette(&self) -> &[Pixel<Rgb>] {
+        &self.palette
+    }
+
+    /// Image width
+    pub fn width(&self) -> usize {
+        self.indices.width()
+    }
+
+    /// Image height
+    pub fn height(&self) -> usize {
+        self.indices.height()
+    }
+
+    /// Image size
+    pub fn size(&self) -> Size {
+        self.indices.size()
+    }
+
+    /// Expand palette indices into an RGB image, indices without a palette entry are black
+    pub fn to_rgb(&self) -> Image<u8, Rgb> {
+        let palette: Vec<[u8; 3]> = self
+            .palette
+            .iter()
+            .map(|px| {
+                let mut data = [0u8; 3];
+                px.copy_to_slice(&mut data);
+                data
+            })
+            .collect();
+
+        let mut dest = Image::new(self.size());
+        dest.for_each(|pt, mut data| {
+            if let Some(color) = palette.get(self.indices.get(pt)[0] as usize) {
+                data.copy_from_slice(color);
+            }
+        });
+        dest
+    }
+}
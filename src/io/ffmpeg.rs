@@ -0,0 +1,223 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::{Color, Error, Image, Rgb};
+
+/// VideoReader decodes video frames using the `ffmpeg` and `ffprobe` commands
+pub struct VideoReader {
+    width: usize,
+    height: usize,
+    fps: f64,
+    child: Child,
+    stdout: ChildStdout,
+}
+
+fn parse_rate(s: &str) -> Option<f64> {
+    match s.split_once('/') {
+        Some((n, d)) => {
+            let n = n.trim().parse::<f64>().ok()?;
+            let d = d.trim().parse::<f64>().ok()?;
+            if d == 0.0 {
+                None
+            } else {
+                Some(n / d)
+            }
+        }
+        None => s.trim().parse::<f64>().ok(),
+    }
+}
+
+/// Get width, height and frame rate of the first video stream using `ffprobe`
+pub fn probe<P: AsRef<Path>>(path: P) -> Result<(usize, usize, f64), Error> {
+    let path = path.as_ref();
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width,height,r_frame_rate",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()?;
+
+    let invalid = || Error::UnableToOpenImage(path.to_string_lossy().to_string());
+
+    if !output.status.success() {
+        return Err(invalid());
+    }
+
+    let s = String::from_utf8_lossy(&output.stdout);
+    let t: Vec<&str> = s.lines().next().unwrap_or_default().split(',').collect();
+    if t.len() < 3 {
+        return Err(invalid());
+    }
+
+    let width = t[0].trim().parse::<usize>().map_err(|_| invalid())?;
+    let height = t[1].trim().parse::<usize>().map_err(|_| invalid())?;
+    let fps = parse_rate(t[2]).ok_or_else(invalid)?;
+    Ok((width, height, fps))
+}
+
+impl VideoReader {
+    /// Open a video file for reading
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<VideoReader, Error> {
+        let path = path.as_ref();
+        let (width, height, fps) = probe(path)?;
+
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-i"])
+            .arg(path)
+            .args(["-f", "rawvideo", "-pix_fmt", "rgb24", "-"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdout = match child.stdout.take() {
+            Some(stdout) => stdout,
+            None => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::UnableToOpenImage(path.to_string_lossy().to_string()));
+            }
+        };
+
+        Ok(VideoReader {
+            width,
+            height,
+            fps,
+            child,
+            stdout,
+        })
+    }
+
+    /// Frame width
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Frame height
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Frames per second
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Read the next frame, returns `Ok(None)` at the end of the stream
+    pub fn read_frame(&mut self) -> Result<Option<Image<u8, Rgb>>, Error> {
+        let mut image = Image::new((self.width, self.height));
+        match self.stdout.read_exact(image.buffer_mut()) {
+            Ok(()) => Ok(Some(image)),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl Iterator for VideoReader {
+    type Item = Image<u8, Rgb>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().ok().flatten()
+    }
+}
+
+impl Drop for VideoReader {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// VideoWriter encodes video frames using the `ffmpeg` command
+pub struct VideoWriter {
+    width: usize,
+    height: usize,
+    child: Child,
+    stdin: Option<ChildStdin>,
+}
+
+impl VideoWriter {
+    /// Create a new video file, the codec is selected by `ffmpeg` based on the file extension
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        fps: f64,
+    ) -> Result<VideoWriter, Error> {
+        let path = path.as_ref();
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-y", "-f", "rawvideo", "-pix_fmt", "rgb24"])
+            .args(["-video_size", &format!("{}x{}", width, height)])
+            .args(["-r", &fps.to_string(), "-i", "-"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()?;
+
+        let stdin = match child.stdin.take() {
+            Some(stdin) => stdin,
+            None => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::UnableToWriteImage(
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+        };
+
+        Ok(VideoWriter {
+            width,
+            height,
+            child,
+            stdin: Some(stdin),
+        })
+    }
+
+    /// Write a single frame, the image must match the size the writer was created with
+    pub fn write_frame(&mut self, image: &Image<u8, Rgb>) -> Result<(), Error> {
+        if image.width() != self.width || image.height() != self.height {
+            return Err(Error::InvalidDimensions(
+                image.width(),
+                image.height(),
+                Rgb::CHANNELS,
+            ));
+        }
+
+        match &mut self.stdin {
+            Some(stdin) => Ok(stdin.write_all(image.buffer())?),
+            None => Err(Error::Message(
+                "VideoWriter is already finished".to_string(),
+            )),
+        }
+    }
+
+    /// Flush all frames and wait for `ffmpeg` to finish writing the file
+    pub fn finish(mut self) -> Result<(), Error> {
+        if let Some(mut stdin) = self.stdin.take() {
+            stdin.flush()?;
+        }
+
+        let status = self.child.wait()?;
+        if !status.success() {
+            return Err(Error::Message(format!("ffmpeg exited with {}", status)));
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for VideoWriter {
+    fn drop(&mut self) {
+        // Closing stdin lets ffmpeg finalize the file
+        self.stdin.take();
+        let _ = self.child.wait();
+    }
+}
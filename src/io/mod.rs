@@ -8,6 +8,10 @@
 /// information about the OpenImageIO bindings
 pub mod magick;
 
+#[cfg(feature = "ffmpeg")]
+/// Video I/O using the `ffmpeg` and `ffprobe` commands
+pub mod ffmpeg;
+
 /// `BaseType` is compatible with OpenImageIO's `TypeDesc::BASETYPE`
 ///
 /// This enum is used to convert from `Type` into a representation that can be used with OIIO
@@ -63,12 +67,34 @@ pub enum BaseType {
     Last,
 }
 
+/// Image information that can be read without decoding pixel data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Image width
+    pub width: usize,
+
+    /// Image height
+    pub height: usize,
+
+    /// Number of channels
+    pub channels: usize,
+
+    /// Data type
+    pub base_type: BaseType,
+
+    /// Number of subimages
+    pub subimages: usize,
+
+    /// Number of MIP levels
+    pub mip_levels: usize,
+}
+
 #[cfg(all(feature = "oiio", not(feature = "docs-rs")))]
 /// OpenImageIO bindings
 pub mod oiio;
 
 #[cfg(all(feature = "oiio", not(feature = "docs-rs")))]
-pub use oiio::{read, write};
+pub use oiio::{decode, encode, probe, read, read_with_meta, write};
 
 #[cfg(feature = "magick")]
 pub use magick::{read, write};
@@ -10,6 +10,7 @@ cpp! {{
     #include <OpenImageIO/imageio.h>
     #include <OpenImageIO/imagebuf.h>
     #include <OpenImageIO/imagebufalgo.h>
+    #include <OpenImageIO/filesystem.h>
     using namespace OIIO;
 }}
 
@@ -50,6 +51,18 @@ impl ImageOutput {
         &mut self.spec
     }
 
+    /// Set the compression method used when writing, for example `"zip"` or `"jpeg:90"`
+    pub fn set_compression(&mut self, name: &str) -> &mut Self {
+        self.spec.set_attr("Compression", name);
+        self
+    }
+
+    /// Set the compression quality (0-100) used when writing lossy formats like JPEG
+    pub fn set_quality(&mut self, q: u32) -> &mut Self {
+        self.spec.set_attr("CompressionQuality", q.min(100) as i32);
+        self
+    }
+
     /// Get the output path
     pub fn path(&self) -> &std::path::Path {
         &self.path
@@ -86,8 +99,15 @@ impl ImageOutput {
 
     /// Write an image to the file
     ///
-    /// Note: `image` dimensions and type will take precendence over the ImageSpec
+    /// Note: `image` dimensions and type will take precendence over the ImageSpec, attributes
+    /// from `image.meta` are added unless they are already set on the ImageSpec
     pub fn write<T: Type, C: Color>(mut self, image: &Image<T, C>) -> Result<(), Error> {
+        for (k, v) in &image.meta.attrs {
+            if self.spec.get_attr(k).is_none() {
+                self.spec.set_attr(k, v);
+            }
+        }
+
         let base_type = T::BASE;
         let path: &std::path::Path = self.path.as_ref();
         let path_str = std::ffi::CString::new(path.to_string_lossy().as_bytes().to_vec()).unwrap();
@@ -120,6 +140,103 @@ impl ImageOutput {
         Ok(())
     }
 
+    /// Open the output file for writing an image one scanline at a time using `write_scanline`,
+    /// `finish` should be called once all scanlines have been written
+    ///
+    /// Note: `meta` dimensions and type will take precendence over the ImageSpec
+    pub fn begin<T: Type, C: Color>(&mut self, meta: &Meta<T, C>) -> Result<(), Error> {
+        let base_type = T::BASE;
+        let path: &std::path::Path = self.path.as_ref();
+        let path_str = std::ffi::CString::new(path.to_string_lossy().as_bytes().to_vec()).unwrap();
+        let filename = path_str.as_ptr();
+        let width = meta.width();
+        let height = meta.height();
+        let channels = C::CHANNELS;
+        let out = self.image_output;
+        let spec = &mut self.spec;
+        let ok = unsafe {
+            cpp!([out as "ImageOutput*",
+              filename as "const char *",
+              base_type as "TypeDesc::BASETYPE",
+              spec as "ImageSpec *",
+              width as "size_t",
+              height as "size_t",
+              channels as "size_t"
+            ] -> bool as "bool" {
+                ImageSpec outspec (*spec);
+                outspec.width = width;
+                outspec.height = height;
+                outspec.nchannels = channels;
+                outspec.channelnames.assign({"R", "G", "B", "A"});
+                outspec.set_format(TypeDesc(base_type));
+                if (!out->open (filename, outspec)) {
+                    return false;
+                }
+                *spec = outspec;
+                return true;
+            })
+        };
+
+        if !ok {
+            return Err(Error::UnableToWriteImage(
+                path.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Write a single row of pixel data, `begin` must be called first
+    pub fn write_scanline<T: Type, C: Color>(&mut self, y: usize, row: &[T]) -> Result<(), Error> {
+        if row.len() < self.spec.width() * C::CHANNELS || y >= self.spec.height() {
+            return Err(Error::InvalidDimensions(
+                row.len() / C::CHANNELS,
+                y,
+                C::CHANNELS,
+            ));
+        }
+
+        let base_type = T::BASE;
+        let pixels = row.as_ptr();
+        let out = self.image_output;
+        let ok = unsafe {
+            cpp!([out as "ImageOutput*",
+              y as "size_t",
+              base_type as "TypeDesc::BASETYPE",
+              pixels as "const void*"
+            ] -> bool as "bool" {
+                return out->write_scanline (y, 0, base_type, pixels);
+            })
+        };
+
+        if !ok {
+            return Err(Error::UnableToWriteImage(
+                self.path.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Finish writing an image started using `begin`
+    pub fn finish(mut self) -> Result<(), Error> {
+        let out = self.image_output;
+        let ok = unsafe {
+            cpp!([out as "ImageOutput*"] -> bool as "bool" {
+                return out->close();
+            })
+        };
+        self.image_output = std::ptr::null_mut();
+
+        if !ok {
+            return Err(Error::UnableToWriteImage(
+                self.path.to_string_lossy().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Append an image to the file for formats with multi-image support
     ///
     /// Note: `image` dimensions and type will take precendence over the ImageSpec
@@ -180,6 +297,7 @@ pub struct ImageInput {
     subimage: usize,
     miplevel: usize,
     image_input: *mut u8,
+    proxy: *mut u8,
 }
 
 impl Drop for ImageInput {
@@ -197,6 +315,16 @@ impl Drop for ImageInput {
         }
 
         self.image_input = std::ptr::null_mut();
+
+        if !self.proxy.is_null() {
+            let proxy = self.proxy;
+            unsafe {
+                cpp!([proxy as "Filesystem::IOProxy*"] {
+                    delete proxy;
+                })
+            }
+            self.proxy = std::ptr::null_mut();
+        }
     }
 }
 
@@ -219,6 +347,37 @@ impl ImageInput {
         self
     }
 
+    /// Get the number of subimages
+    pub fn num_subimages(&self) -> usize {
+        let input = self.image_input;
+        unsafe {
+            cpp!([input as "std::unique_ptr<ImageInput>"] -> usize as "size_t" {
+                int n = 0;
+                while (input->seek_subimage(n, 0)) {
+                    n += 1;
+                }
+                input->seek_subimage(0, 0);
+                return (size_t)n;
+            })
+        }
+    }
+
+    /// Get the number of MIP levels for the current subimage
+    pub fn num_miplevels(&self) -> usize {
+        let input = self.image_input;
+        let subimage = self.subimage;
+        unsafe {
+            cpp!([input as "std::unique_ptr<ImageInput>", subimage as "size_t"] -> usize as "size_t" {
+                int n = 0;
+                while (input->seek_subimage(subimage, n)) {
+                    n += 1;
+                }
+                input->seek_subimage(0, 0);
+                return (size_t)n;
+            })
+        }
+    }
+
     /// Get input image spec
     pub fn spec(&self) -> &ImageSpec {
         &self.spec
@@ -271,19 +430,90 @@ impl ImageInput {
             subimage: 0,
             miplevel: 0,
             path: path.to_path_buf(),
+            proxy: std::ptr::null_mut(),
         })
     }
 
+    /// Open in-memory image for reading, the returned `ImageInput` must not outlive `data`
+    fn open_memory(data: &[u8], format_hint: Option<&str>) -> Result<ImageInput, Error> {
+        let mut spec = ImageSpec::empty();
+        let tmp = &mut spec;
+
+        let path = match format_hint {
+            Some(ext) => std::path::PathBuf::from(format!("memory.{ext}")),
+            None => std::path::PathBuf::from("memory"),
+        };
+        let path_str = std::ffi::CString::new(path.to_string_lossy().as_bytes().to_vec()).unwrap();
+        let filename = path_str.as_ptr();
+        let len = data.len();
+        let data = data.as_ptr();
+        let mut proxy: *mut u8 = std::ptr::null_mut();
+        let proxy_ptr = &mut proxy;
+
+        let input = unsafe {
+            cpp!([filename as "const char *",
+              tmp as "ImageSpec*",
+              data as "const unsigned char*",
+              len as "size_t",
+              proxy_ptr as "Filesystem::IOProxy**"
+            ] ->  *mut u8 as "std::unique_ptr<ImageInput>" {
+                std::string s(filename);
+                auto proxy = new Filesystem::IOMemReader((void*)data, len);
+                auto input = ImageInput::open(s, nullptr, proxy);
+                if (!input) {
+                    delete proxy;
+                    return nullptr;
+                }
+
+                *proxy_ptr = proxy;
+                *tmp = input->spec();
+
+                return input;
+            })
+        };
+
+        if input.is_null() {
+            return Err(Error::UnableToOpenImage(path.to_string_lossy().to_string()));
+        }
+
+        Ok(ImageInput {
+            spec,
+            image_input: input,
+            subimage: 0,
+            miplevel: 0,
+            path,
+            proxy,
+        })
+    }
+
+    /// Get the spec for the given subimage
+    fn subimage_spec(&self, subimage: usize) -> ImageSpec {
+        let input = self.image_input;
+        unsafe {
+            cpp!([input as "std::unique_ptr<ImageInput>", subimage as "size_t"] -> ImageSpec as "ImageSpec" {
+                return input->spec(subimage, 0);
+            })
+        }
+    }
+
     /// Read into existing Image
     pub fn read_into<T: Type, C: Color>(&self, image: &mut Image<T, C>) -> Result<(), Error> {
+        self.read_subimage_into(self.subimage, &self.spec, image)
+    }
+
+    fn read_subimage_into<T: Type, C: Color>(
+        &self,
+        subimage: usize,
+        spec: &ImageSpec,
+        image: &mut Image<T, C>,
+    ) -> Result<(), Error> {
         let data = image.data.as_mut_ptr();
 
         let channels = C::CHANNELS;
 
         let input = self.image_input;
-        let index = self.subimage;
+        let index = subimage;
         let miplevel = self.miplevel;
-        let spec = &self.spec;
         let fmt = T::BASE;
 
         if spec.nchannels() < C::CHANNELS
@@ -322,27 +552,159 @@ impl ImageInput {
     ///
     /// Note: the `convert` method may be called if the requested color doesn't match
     pub fn read<T: Type, C: Color>(&self) -> Result<Image<T, C>, Error> {
+        self.read_subimage(self.subimage, &self.spec)
+    }
+
+    /// Read a region of the image into an existing image, only the scanlines or tiles
+    /// overlapping `roi` are read from disk
+    pub fn read_region_into<T: Type, C: Color>(
+        &self,
+        roi: Region,
+        image: &mut Image<T, C>,
+    ) -> Result<(), Error> {
+        let spec = &self.spec;
+        if roi.max_x() > spec.width() || roi.max_y() > spec.height() {
+            return Err(Error::OutOfBounds(roi.max_x(), roi.max_y()));
+        }
+
+        if spec.nchannels() < C::CHANNELS
+            || roi.width() != image.width()
+            || roi.height() != image.height()
+        {
+            return Err(Error::InvalidDimensions(
+                roi.width(),
+                roi.height(),
+                spec.nchannels(),
+            ));
+        }
+
+        let input = self.image_input;
+        let subimage = self.subimage;
+        let miplevel = self.miplevel;
+        let channels = C::CHANNELS;
+        let fmt = T::BASE;
+        let (tile_width, tile_height) = (spec.tile_width(), spec.tile_height());
+
+        // Bounds of the data read from disk, tiled images must be read using tile-aligned bounds
+        let tiled = tile_width > 0 && tile_height > 0;
+        let (x0, x1, band) = if tiled {
+            (
+                roi.min_x() / tile_width * tile_width,
+                (roi.max_x().div_ceil(tile_width) * tile_width).min(spec.width()),
+                tile_height,
+            )
+        } else {
+            (0, spec.width(), 1)
+        };
+
+        let mut y0 = roi.min_y() / band * band;
+        let mut buffer = vec![T::default(); (x1 - x0) * band * channels];
+        while y0 < roi.max_y() {
+            let y1 = (y0 + band).min(spec.height());
+            let data = buffer.as_mut_ptr();
+            let res = unsafe {
+                cpp!([input as "std::unique_ptr<ImageInput>",
+                  subimage as "size_t",
+                  miplevel as "size_t",
+                  tiled as "bool",
+                  x0 as "size_t",
+                  x1 as "size_t",
+                  y0 as "size_t",
+                  y1 as "size_t",
+                  channels as "size_t",
+                  fmt as "TypeDesc::BASETYPE",
+                  data as "void *"
+                ] -> bool as "bool" {
+                    if (tiled) {
+                        return input->read_tiles(subimage, miplevel, x0, x1, y0, y1, 0, 1, 0, channels, fmt, data);
+                    }
+                    return input->read_scanlines(subimage, miplevel, y0, y1, 0, 0, channels, fmt, data);
+                })
+            };
+
+            if !res {
+                return Err(Error::CannotReadImage(
+                    self.path.to_string_lossy().to_string(),
+                ));
+            }
+
+            let row_len = (x1 - x0) * channels;
+            for y in y0.max(roi.min_y())..y1.min(roi.max_y()) {
+                let start = (y - y0) * row_len + (roi.min_x() - x0) * channels;
+                let row = &buffer[start..start + roi.width() * channels];
+                image.row_mut(y - roi.min_y()).copy_from_slice(row);
+            }
+
+            y0 = y1;
+        }
+
+        Ok(())
+    }
+
+    /// Read a region of the image into a new image
+    ///
+    /// Note: the `convert` method may be called if the requested color doesn't match
+    pub fn read_region<T: Type, C: Color>(&self, roi: Region) -> Result<Image<T, C>, Error> {
         let nchannels = self.spec.nchannels();
+        let size = roi.size;
+
+        if C::CHANNELS != nchannels {
+            if nchannels == 1 {
+                let mut image = Image::<f32, Gray>::new(size);
+                self.read_region_into(roi, &mut image)?;
+                Ok(image.convert())
+            } else if nchannels == 4 {
+                let mut image = Image::<f32, Rgba>::new(size);
+                self.read_region_into(roi, &mut image)?;
+                Ok(image.convert())
+            } else {
+                let mut image = Image::<f32, Rgb>::new(size);
+                self.read_region_into(roi, &mut image)?;
+                Ok(image.convert())
+            }
+        } else {
+            let mut image = Image::new(size);
+            self.read_region_into(roi, &mut image)?;
+            Ok(image)
+        }
+    }
+
+    /// Read every subimage into a new image
+    ///
+    /// Note: the `convert` method may be called if the requested color doesn't match
+    pub fn read_all<T: Type, C: Color>(&self) -> Result<Vec<Image<T, C>>, Error> {
+        (0..self.num_subimages())
+            .map(|i| self.read_subimage(i, &self.subimage_spec(i)))
+            .collect()
+    }
+
+    fn read_subimage<T: Type, C: Color>(
+        &self,
+        subimage: usize,
+        spec: &ImageSpec,
+    ) -> Result<Image<T, C>, Error> {
+        let nchannels = spec.nchannels();
+        let size = (spec.width(), spec.height());
 
         // `convert` is called if the channels don't match the image on disk or the color is not
         // Gray, Rgb, or Rgba
         if C::CHANNELS != nchannels {
             if nchannels == 1 {
-                let mut image = Image::<f32, Gray>::new((self.spec.width(), self.spec.height()));
-                self.read_into(&mut image)?;
+                let mut image = Image::<f32, Gray>::new(size);
+                self.read_subimage_into(subimage, spec, &mut image)?;
                 Ok(image.convert())
             } else if nchannels == 4 {
-                let mut image = Image::<f32, Rgba>::new((self.spec.width(), self.spec.height()));
-                self.read_into(&mut image)?;
+                let mut image = Image::<f32, Rgba>::new(size);
+                self.read_subimage_into(subimage, spec, &mut image)?;
                 Ok(image.convert())
             } else {
-                let mut image = Image::<f32, Rgb>::new((self.spec.width(), self.spec.height()));
-                self.read_into(&mut image)?;
+                let mut image = Image::<f32, Rgb>::new(size);
+                self.read_subimage_into(subimage, spec, &mut image)?;
                 Ok(image.convert())
             }
         } else {
-            let mut image = Image::new((self.spec.width(), self.spec.height()));
-            self.read_into(&mut image)?;
+            let mut image = Image::new(size);
+            self.read_subimage_into(subimage, spec, &mut image)?;
             Ok(image)
         }
     }
@@ -379,6 +741,59 @@ impl<'a> From<&'a str> for Attr<'a> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// `AttrOwned` is an owned version of `Attr` that can outlive the `ImageSpec` it was read from
+pub enum AttrOwned {
+    /// Integer value
+    Int(i32),
+
+    /// Float value
+    Float(f32),
+
+    /// String value
+    String(String),
+}
+
+impl<'a> From<Attr<'a>> for AttrOwned {
+    fn from(attr: Attr<'a>) -> AttrOwned {
+        match attr {
+            Attr::Int(i) => AttrOwned::Int(i),
+            Attr::Float(f) => AttrOwned::Float(f),
+            Attr::String(s) => AttrOwned::String(s.to_string()),
+        }
+    }
+}
+
+impl From<AttrOwned> for MetaAttr {
+    fn from(attr: AttrOwned) -> MetaAttr {
+        match attr {
+            AttrOwned::Int(i) => MetaAttr::Int(i),
+            AttrOwned::Float(f) => MetaAttr::Float(f),
+            AttrOwned::String(s) => MetaAttr::String(s),
+        }
+    }
+}
+
+impl<'a> From<&'a MetaAttr> for Attr<'a> {
+    fn from(attr: &'a MetaAttr) -> Attr<'a> {
+        match attr {
+            MetaAttr::Int(i) => Attr::Int(*i),
+            MetaAttr::Float(f) => Attr::Float(*f),
+            MetaAttr::String(s) => Attr::String(s.as_str()),
+        }
+    }
+}
+
+impl<'a> From<&'a AttrOwned> for Attr<'a> {
+    fn from(attr: &'a AttrOwned) -> Attr<'a> {
+        match attr {
+            AttrOwned::Int(i) => Attr::Int(*i),
+            AttrOwned::Float(f) => Attr::Float(*f),
+            AttrOwned::String(s) => Attr::String(s.as_str()),
+        }
+    }
+}
+
 cpp_class!(
     /// ImageSpec wraps `OIIO::ImageSpec`
     pub unsafe struct ImageSpec as "ImageSpec"
@@ -433,6 +848,24 @@ impl ImageSpec {
         }
     }
 
+    /// Get tile width, this will be 0 for scanline images
+    pub fn tile_width(&self) -> usize {
+        unsafe {
+            cpp!([self as "const ImageSpec*"] -> usize as "size_t" {
+                return (size_t)self->tile_width;
+            })
+        }
+    }
+
+    /// Get tile height, this will be 0 for scanline images
+    pub fn tile_height(&self) -> usize {
+        unsafe {
+            cpp!([self as "const ImageSpec*"] -> usize as "size_t" {
+                return (size_t)self->tile_height;
+            })
+        }
+    }
+
     /// Get image format
     pub fn format(&self) -> BaseType {
         unsafe {
@@ -499,6 +932,14 @@ impl ImageSpec {
         }
     }
 
+    /// Get the EXIF orientation (1-8), if any
+    pub fn orientation(&self) -> Option<i32> {
+        match self.get_attr("Orientation") {
+            Some(Attr::Int(i)) => Some(i),
+            _ => None,
+        }
+    }
+
     /// Return the number of subimages, if any
     pub fn subimages(&self) -> Option<i32> {
         match self.get_attr("oiio:ColorSpace") {
@@ -538,6 +979,16 @@ impl ImageSpec {
     }
 }
 
+impl ImageSpec {
+    /// Get a map with all attributes, copying keys and values so they can outlive the `ImageSpec`
+    pub fn attrs_owned(&self) -> std::collections::BTreeMap<String, AttrOwned> {
+        self.attrs()
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.into()))
+            .collect()
+    }
+}
+
 pub(crate) mod internal {
     use super::*;
 
@@ -679,6 +1130,107 @@ pub fn read<P: AsRef<std::path::Path>, T: Type, C: Color>(path: P) -> Result<Ima
     ImageInput::open(path, None)?.read()
 }
 
+/// Read an image and its attributes from disk
+pub fn read_with_meta<P: AsRef<std::path::Path>, T: Type, C: Color>(
+    path: P,
+) -> Result<(Image<T, C>, std::collections::BTreeMap<String, AttrOwned>), Error> {
+    let input = ImageInput::open(path, None)?;
+    let mut image: Image<T, C> = input.read()?;
+    let attrs = input.spec().attrs_owned();
+    image.meta.attrs = attrs
+        .iter()
+        .map(|(k, v)| (k.clone(), MetaAttr::from(v.clone())))
+        .collect();
+    Ok((image, attrs))
+}
+
+/// Read image information from disk without decoding any pixel data
+pub fn probe<P: AsRef<std::path::Path>>(path: P) -> Result<super::ImageInfo, Error> {
+    let input = ImageInput::open(path, None)?;
+    let spec = input.spec();
+    Ok(super::ImageInfo {
+        width: spec.width(),
+        height: spec.height(),
+        channels: spec.nchannels(),
+        base_type: spec.format(),
+        subimages: input.num_subimages(),
+        mip_levels: input.num_miplevels(),
+    })
+}
+
+/// Decode an image from an in-memory buffer, `format_hint` should be set to the file extension
+/// of the encoded format (for example `"png"`) when known
+pub fn decode<T: Type, C: Color>(
+    data: &[u8],
+    format_hint: Option<&str>,
+) -> Result<Image<T, C>, Error> {
+    ImageInput::open_memory(data, format_hint)?.read()
+}
+
+/// Encode an image to an in-memory buffer, `format` is the file extension of the output format
+/// (for example `"png"`)
+pub fn encode<T: Type, C: Color>(image: &Image<T, C>, format: &str) -> Result<Vec<u8>, Error> {
+    let filename = format!("memory.{format}");
+    let filename_str = std::ffi::CString::new(filename.as_bytes().to_vec()).unwrap();
+    let filename_ptr = filename_str.as_ptr();
+    let base_type = T::BASE;
+    let pixels = image.data.as_ptr();
+    let (width, height, channels) = image.shape();
+
+    let buffer = unsafe {
+        cpp!([filename_ptr as "const char *",
+          base_type as "TypeDesc::BASETYPE",
+          width as "size_t",
+          height as "size_t",
+          channels as "size_t",
+          pixels as "const void*"
+        ] -> *mut u8 as "std::vector<unsigned char>*" {
+            auto out = ImageOutput::create(filename_ptr);
+            if (!out) {
+                return nullptr;
+            }
+
+            auto buffer = new std::vector<unsigned char>();
+            Filesystem::IOVecOutput vecout(*buffer);
+            void *ptr = &vecout;
+
+            ImageSpec outspec(width, height, channels, TypeDesc(base_type));
+            outspec.channelnames.assign({"R", "G", "B", "A"});
+            outspec.attribute("oiio:ioproxy", TypeDesc::PTR, &ptr);
+            if (!out->open(filename_ptr, outspec) || !out->write_image(base_type, pixels)) {
+                delete buffer;
+                return nullptr;
+            }
+
+            out->close();
+            return buffer;
+        })
+    };
+
+    if buffer.is_null() {
+        return Err(Error::UnableToWriteImage(filename));
+    }
+
+    let mut len = 0;
+    let len_ptr = &mut len;
+    let data = unsafe {
+        cpp!([buffer as "std::vector<unsigned char>*", len_ptr as "size_t*"] -> *const u8 as "const unsigned char*" {
+            *len_ptr = buffer->size();
+            return buffer->data();
+        })
+    };
+
+    let dest = unsafe { std::slice::from_raw_parts(data, len).to_vec() };
+
+    unsafe {
+        cpp!([buffer as "std::vector<unsigned char>*"] {
+            delete buffer;
+        })
+    }
+
+    Ok(dest)
+}
+
 /// Write image to disk
 pub fn write<P: AsRef<std::path::Path>, T: Type, C: Color>(
     path: P,
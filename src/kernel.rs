@@ -10,13 +10,20 @@ pub struct Kernel {
     rows: usize,
     cols: usize,
     data: Vec<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    bias: f64,
 }
 
 impl From<Vec<Vec<f64>>> for Kernel {
     fn from(data: Vec<Vec<f64>>) -> Kernel {
         let rows = data.len();
         let cols = data[0].len();
-        Kernel { data, rows, cols }
+        Kernel {
+            data,
+            rows,
+            cols,
+            bias: 0.0,
+        }
     }
 }
 
@@ -32,6 +39,7 @@ impl<'a> From<&'a [&'a [f64]]> for Kernel {
             data: v,
             rows,
             cols,
+            bias: 0.0,
         }
     }
 }
@@ -43,6 +51,7 @@ impl<const N: usize> From<[[f64; N]; N]> for Kernel {
             data,
             rows: N,
             cols: N,
+            bias: 0.0,
         }
     }
 }
@@ -68,6 +77,7 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Kernel {
                 }
             }
         }
+        f.map(|x| x + self.bias);
         f.copy_to_slice(dest);
     }
 }
@@ -76,7 +86,12 @@ impl Kernel {
     /// Create a new kernel with the given number of rows and columns
     pub fn new(rows: usize, cols: usize) -> Kernel {
         let data = vec![vec![0.0; cols]; rows];
-        Kernel { data, rows, cols }
+        Kernel {
+            data,
+            rows,
+            cols,
+            bias: 0.0,
+        }
     }
 
     /// Create a new, square kernel
@@ -84,6 +99,17 @@ impl Kernel {
         Self::new(x, x)
     }
 
+    /// Set a constant offset that is added to each color channel after convolution
+    pub fn with_bias(mut self, bias: f64) -> Kernel {
+        self.bias = bias;
+        self
+    }
+
+    /// Get the bias
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
     /// Ensures the sum of the kernel is <= 1
     pub fn normalize(&mut self) {
         let sum: f64 = self.data.iter().map(|x| -> f64 { x.iter().sum() }).sum();
@@ -110,12 +136,28 @@ impl Kernel {
         k
     }
 
+    /// Create a new, square kernel and fill it by executing `f` with the (x, y) offset of each
+    /// element from the center of the kernel, returns an error if `n` is even
+    pub fn from_fn_centered<F: Fn(isize, isize) -> f64>(n: usize, f: F) -> Result<Kernel, Error> {
+        if n & 1 == 0 {
+            return Err(Error::Message(format!(
+                "Centered kernel size must be odd, got {}",
+                n
+            )));
+        }
+        Ok(Kernel::centered(n, f))
+    }
+
+    fn centered<F: Fn(isize, isize) -> f64>(n: usize, f: F) -> Kernel {
+        let c = (n / 2) as isize;
+        Kernel::create(n, n, |i, j| f(i as isize - c, j as isize - c))
+    }
+
     /// Generate gaussian blur kernel
     pub fn gaussian(n: usize, std: f64) -> Kernel {
-        assert!(n % 2 != 0);
         let std2 = std * std;
         let a = 1.0 / (2.0 * f64::consts::PI * std2);
-        let mut k = Kernel::create(n, n, |i, j| {
+        let mut k = Kernel::centered(n, |i, j| {
             let x = (i * i + j * j) as f64 / (2.0 * std2);
             a * f64::consts::E.powf(-1.0 * x)
         });
@@ -148,6 +190,7 @@ impl Kernel {
         Kernel {
             rows: 3,
             cols: 3,
+            bias: 0.0,
             data: vec![
                 vec![1.0, 0.0, -1.0],
                 vec![2.0, 0.0, -2.0],
@@ -161,6 +204,7 @@ impl Kernel {
         Kernel {
             rows: 3,
             cols: 3,
+            bias: 0.0,
             data: vec![
                 vec![1.0, 2.0, 1.0],
                 vec![0.0, 0.0, 0.0],
@@ -174,10 +218,51 @@ impl Kernel {
         Kernel::from([[0., -1., 0.], [-1., 4., -1.], [0., -1., 0.]])
     }
 
+    /// Laplacian of gaussian, the sign matches `Kernel::laplacian` so the center is positive. The
+    /// kernel is adjusted to sum to zero
+    pub fn laplacian_of_gaussian(n: usize, std: f64) -> Kernel {
+        let std2 = std * std;
+        let mut k = Kernel::centered(n, |i, j| {
+            let x = (i * i + j * j) as f64 / (2.0 * std2);
+            (1.0 - x) * (-x).exp() / (f64::consts::PI * std2 * std2)
+        });
+        let mean = k.data.iter().flatten().sum::<f64>() / (n * n) as f64;
+        k.data.iter_mut().flatten().for_each(|x| *x -= mean);
+        k
+    }
+
+    /// Difference of gaussians, `std1` should be smaller than `std2` for a positive center
+    pub fn difference_of_gaussians(n: usize, std1: f64, std2: f64) -> Kernel {
+        Kernel::gaussian(n, std1) - Kernel::gaussian(n, std2)
+    }
+
     /// Sobel X and Y combined
     pub fn sobel() -> Kernel {
         Kernel::sobel_x() + Kernel::sobel_y()
     }
+
+    /// 3x3 emboss kernel, `direction` is the angle of the light source in radians. The kernel sums
+    /// to zero, so flat areas produce zero unless a bias is set using `Kernel::with_bias`
+    pub fn emboss(direction: f64) -> Kernel {
+        let (dy, dx) = direction.sin_cos();
+        Kernel::centered(3, |i, j| i as f64 * dx + j as f64 * dy)
+    }
+
+    /// Normalized motion blur kernel covering a line of `length` pixels through the center at
+    /// `angle` radians
+    pub fn motion_blur(length: usize, angle: f64) -> Kernel {
+        let n = length.max(1) | 1;
+        let half = (length.max(1) - 1) as f64 / 2.0;
+        let (dy, dx) = angle.sin_cos();
+        let mut k = Kernel::centered(n, |i, j| {
+            let (i, j) = (i as f64, j as f64);
+            let t = (i * dx + j * dy).clamp(-half, half);
+            let dist = ((i - t * dx).powi(2) + (j - t * dy).powi(2)).sqrt();
+            (1.0 - dist).max(0.0)
+        });
+        k.normalize();
+        k
+    }
 }
 
 impl ops::Add for Kernel {
@@ -231,3 +316,98 @@ impl ops::Div for Kernel {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_gaussian_centered() {
+        for n in [3, 5, 7, 9] {
+            let k = Kernel::gaussian(n, 1.4);
+            let c = n / 2;
+            let center = k.data[c][c];
+            for row in &k.data {
+                for x in row {
+                    assert!(*x <= center);
+                }
+            }
+            assert!(k.data[0][0] == k.data[n - 1][n - 1]);
+        }
+    }
+
+    #[test]
+    fn test_gaussian_symmetric() {
+        let k = Kernel::gaussian(5, 1.4);
+
+        let mut hmirror = k.clone();
+        hmirror.data.iter_mut().for_each(|row| row.reverse());
+        assert!(k == hmirror);
+
+        let mut vmirror = k.clone();
+        vmirror.data.reverse();
+        assert!(k == vmirror);
+
+        let max = k.data.iter().flatten().cloned().fold(f64::MIN, f64::max);
+        assert!(k.data[2][2] == max);
+    }
+
+    #[test]
+    fn test_from_fn_centered() {
+        let k = Kernel::from_fn_centered(3, |i, j| (i * 3 + j) as f64).unwrap();
+        assert!(k.data[1][1] == 0.0);
+        assert!(k.data[0][2] == 2.0);
+        assert!(Kernel::from_fn_centered(4, |_, _| 1.0).is_err());
+    }
+
+    #[test]
+    fn test_emboss() {
+        let k = Kernel::emboss(std::f64::consts::FRAC_PI_4).with_bias(0.5);
+        let sum: f64 = k.data.iter().flatten().sum();
+        assert!(sum.abs() < 1e-12);
+
+        let image = Image::<f32, Rgb>::generate((8, 8), |_, _| Pixel::from(vec![0.5, 0.5, 0.5]));
+        let out: Image<f32, Rgb> = image.run(k, None);
+        for y in 1..7 {
+            for x in 1..7 {
+                assert!(out
+                    .get((x, y))
+                    .as_slice()
+                    .iter()
+                    .all(|x| (x - 0.5).abs() < 1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn test_motion_blur() {
+        let k = Kernel::motion_blur(5, 0.0);
+        assert_eq!((k.rows, k.cols), (5, 5));
+        let row: f64 = k.data[2].iter().sum();
+        assert!((row - 1.0).abs() < 1e-12);
+
+        let k = Kernel::motion_blur(7, 0.7);
+        let sum: f64 = k.data.iter().flatten().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_kernel_bias() {
+        let image = Image::<f32, Rgb>::generate((8, 8), |x, y| Pixel::from(vec![x, y, 0.25]));
+        let out: Image<f32, Rgb> = image.run(Kernel::square(3).with_bias(0.5), None);
+        assert!(out.data().iter().all(|x| (x - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_band_pass() {
+        for k in [
+            Kernel::laplacian_of_gaussian(9, 1.4),
+            Kernel::difference_of_gaussians(9, 1.0, 2.0),
+        ] {
+            let sum: f64 = k.data.iter().flatten().sum();
+            assert!(sum.abs() < 1e-9);
+            assert!(k.data[4][4] > 0.0);
+            assert!(k.data[0][4] < 0.0);
+        }
+    }
+}
@@ -34,6 +34,7 @@
 /// 16-bit float
 pub use half::f16;
 
+mod bit_image;
 mod color;
 mod data;
 mod error;
@@ -43,9 +44,11 @@ mod hash;
 mod histogram;
 mod image;
 mod image_data;
+mod indexed;
 mod meta;
 mod pixel;
 mod r#type;
+mod view;
 
 /// Text
 #[cfg(feature = "text")]
@@ -60,25 +63,30 @@ pub mod kernel;
 /// Image transforms
 pub mod transform;
 
-pub use crate::meta::Meta;
-pub use color::{Channel, Cmyk, Color, Gray, Hsv, Rgb, Rgba, Srgb, Srgba, Xyz, Yuv};
+pub use crate::meta::{Meta, MetaAttr};
+pub use bit_image::BitImage;
+pub use color::{
+    Channel, Cmyk, Color, Gray, Hsl, Hsv, Lab, Multi, Rgb, Rgba, Srgb, Srgba, Xyz, Yuv,
+};
 pub use data::{Data, DataMut};
 pub use error::Error;
 pub use filters::{
     filter, AsyncFilter, AsyncMode, AsyncPipeline, Filter, FilterExt, Input, Pipeline, Schedule,
 };
-pub use geom::{Point, Region, Size};
+pub use geom::{Point, PointExt, Region, RegionExt, Size, SizeExt};
 pub use hash::Hash;
 pub use histogram::Histogram;
-pub use image::Image;
+pub use image::{BorderMode, ChannelStats, Connectivity, Image, InterpolationMode};
 pub use image_data::ImageData;
+pub use indexed::IndexedImage;
 pub use kernel::Kernel;
-pub use pixel::Pixel;
-pub use r#type::Type;
-pub use transform::Transform;
+pub use pixel::{DistanceMetric, Pixel};
+pub use r#type::{Bit, Type};
+pub use transform::{Perspective, Transform};
+pub use view::ImageView;
 
 #[cfg(feature = "mmap")]
-pub use image_data::mmap::Mmap;
+pub use image_data::mmap::{Mmap, MmapReadOnly, ReadOnlyImage};
 
 #[cfg(test)]
 mod tests;
@@ -3,14 +3,72 @@ use crate::*;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
+/// Image attribute value stored in `Meta`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetaAttr {
+    /// Integer value
+    Int(i32),
+
+    /// Float value
+    Float(f32),
+
+    /// String value
+    String(String),
+}
+
+/// Floats are compared by their bit pattern, this keeps `Eq` on `Meta`
+impl PartialEq for MetaAttr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MetaAttr::Int(a), MetaAttr::Int(b)) => a == b,
+            (MetaAttr::Float(a), MetaAttr::Float(b)) => a.to_bits() == b.to_bits(),
+            (MetaAttr::String(a), MetaAttr::String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for MetaAttr {}
+
+impl From<i32> for MetaAttr {
+    fn from(i: i32) -> MetaAttr {
+        MetaAttr::Int(i)
+    }
+}
+
+impl From<f32> for MetaAttr {
+    fn from(f: f32) -> MetaAttr {
+        MetaAttr::Float(f)
+    }
+}
+
+impl From<&str> for MetaAttr {
+    fn from(s: &str) -> MetaAttr {
+        MetaAttr::String(s.to_string())
+    }
+}
+
+impl From<String> for MetaAttr {
+    fn from(s: String) -> MetaAttr {
+        MetaAttr::String(s)
+    }
+}
+
 /// Image metadata
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Meta<T: Type, C: Color> {
     /// Image size
     pub size: Size,
+
+    /// Image attributes (EXIF data, camera information, etc), these are copied to images created
+    /// using `Image::new_like`, `Image::convert` and `Image::run` and written by `Image::save`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attrs: BTreeMap<String, MetaAttr>,
     _type: PhantomData<T>,
     _color: PhantomData<C>,
 }
@@ -20,11 +78,22 @@ impl<T: Type, C: Color> Meta<T, C> {
     pub fn new(size: impl Into<Size>) -> Meta<T, C> {
         Meta {
             size: size.into(),
+            attrs: BTreeMap::new(),
             _type: PhantomData,
             _color: PhantomData,
         }
     }
 
+    /// Get an attribute
+    pub fn attr(&self, key: impl AsRef<str>) -> Option<&MetaAttr> {
+        self.attrs.get(key.as_ref())
+    }
+
+    /// Set an attribute
+    pub fn set_attr(&mut self, key: impl Into<String>, value: impl Into<MetaAttr>) {
+        self.attrs.insert(key.into(), value.into());
+    }
+
     /// Returns the size of a row
     #[inline]
     pub fn width_step(&self) -> usize {
@@ -1,10 +1,39 @@
 use crate::*;
 
+/// Distance metric used by `Pixel::distance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DistanceMetric {
+    /// Square root of the sum of squared differences
+    Euclidean,
+
+    /// Sum of absolute differences
+    Manhattan,
+}
+
 /// Normalized image data
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Pixel<C: Color>(Box<[f64]>, std::marker::PhantomData<C>);
 
+/// sRGB transfer function, linear -> sRGB
+fn srgb_encode(x: f64) -> f64 {
+    if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Inverse sRGB transfer function, sRGB -> linear
+fn srgb_decode(x: f64) -> f64 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
 impl<C: Color> AsRef<[f64]> for Pixel<C> {
     fn as_ref(&self) -> &[f64] {
         self.0.as_ref()
@@ -228,6 +257,26 @@ impl<C: Color> Pixel<C> {
         self
     }
 
+    /// Add `other` to `self`, clamping the results between 0 and 1.0
+    pub fn saturating_add(&mut self, other: &Pixel<C>) -> &mut Self {
+        self.map2(other, |x, y| (x + y).clamp(0., 1.))
+    }
+
+    /// Subtract `other` from `self`, clamping the results between 0 and 1.0
+    pub fn saturating_sub(&mut self, other: &Pixel<C>) -> &mut Self {
+        self.map2(other, |x, y| (x - y).clamp(0., 1.))
+    }
+
+    /// Multiply `self` by `other`, clamping the results between 0 and 1.0
+    pub fn saturating_mul(&mut self, other: &Pixel<C>) -> &mut Self {
+        self.map2(other, |x, y| (x * y).clamp(0., 1.))
+    }
+
+    /// Divide `self` by `other`, clamping the results between 0 and 1.0
+    pub fn saturating_div(&mut self, other: &Pixel<C>) -> &mut Self {
+        self.map2(other, |x, y| (x / y).clamp(0., 1.))
+    }
+
     /// Apply `f` for each channel in a pixel
     pub fn for_each(&self, mut f: impl FnMut(usize, f64)) {
         for i in 0..self.len() {
@@ -266,6 +315,57 @@ impl<C: Color> Pixel<C> {
     pub fn gamma_lin(&mut self) -> &mut Self {
         self.gamma(2.2)
     }
+
+    /// Encode linear values using the piecewise sRGB transfer function
+    pub fn to_srgb(&mut self) -> &mut Self {
+        self.map(srgb_encode)
+    }
+
+    /// Decode sRGB encoded values to linear using the piecewise sRGB transfer function
+    pub fn to_linear(&mut self) -> &mut Self {
+        self.map(srgb_decode)
+    }
+
+    /// Distance between two pixels, the alpha channel is ignored
+    pub fn distance(&self, other: &Pixel<C>, metric: DistanceMetric) -> f64 {
+        let diff = self.iter().zip(other.iter()).map(|(a, b)| a - b);
+        match metric {
+            DistanceMetric::Euclidean => diff.map(|d| d * d).sum::<f64>().sqrt(),
+            DistanceMetric::Manhattan => diff.map(f64::abs).sum(),
+        }
+    }
+
+    /// Linear interpolation between `self` and `other`, `t = 0` returns `self` and `t = 1`
+    /// returns `other`. Unlike most pixel operations this includes the alpha channel
+    pub fn lerp(&self, other: &Pixel<C>, t: f64) -> Pixel<C> {
+        let mut dest = self.clone();
+        for i in 0..C::CHANNELS {
+            dest[i] = self[i] + (other[i] - self[i]) * t;
+        }
+        dest
+    }
+}
+
+impl Pixel<Lab> {
+    /// CIE76 color difference
+    pub fn delta_e(&self, other: &Pixel<Lab>) -> f64 {
+        ((self[0] - other[0]).powi(2) + (self[1] - other[1]).powi(2) + (self[2] - other[2]).powi(2))
+            .sqrt()
+    }
+
+    /// CIE94 color difference, using the graphic arts weighting factors
+    pub fn delta_e94(&self, other: &Pixel<Lab>) -> f64 {
+        let dl = self[0] - other[0];
+        let c1 = (self[1] * self[1] + self[2] * self[2]).sqrt();
+        let c2 = (other[1] * other[1] + other[2] * other[2]).sqrt();
+        let dc = c1 - c2;
+        let da = self[1] - other[1];
+        let db = self[2] - other[2];
+        let dh = (da * da + db * db - dc * dc).max(0.0).sqrt();
+        let sc = 1.0 + 0.045 * c1;
+        let sh = 1.0 + 0.015 * c1;
+        (dl * dl + (dc / sc).powi(2) + (dh / sh).powi(2)).sqrt()
+    }
 }
 
 impl<T: Type, C: Color> std::iter::FromIterator<T> for Pixel<C> {
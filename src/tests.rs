@@ -70,6 +70,25 @@ fn test_invert_async() {
     assert!(dest.save("images/test-invert-async.jpg").is_ok());
 }
 
+#[cfg(feature = "tokio")]
+#[test]
+fn test_invert_tokio() {
+    let image = Image::<f32, Rgb>::generate((16, 8), |x, y| {
+        Pixel::from(vec![x as f64 / 16.0, y as f64 / 8.0, 0.5])
+    });
+    let mut expected = image.new_like();
+    expected.apply(invert(), &[&image]);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let mut dest = image.new_like();
+    runtime.block_on(async {
+        dest.apply_tokio(invert(), &[&image]).await;
+    });
+    assert!(dest == expected);
+}
+
 #[test]
 fn test_hash() {
     let mut a: Image<f32, Rgb> = Image::open("images/A.exr").unwrap();
@@ -245,6 +264,134 @@ fn test_metadata() {
     assert!(input2.spec().get_attr("testing") == Some(Attr::String("123")));
 }
 
+#[cfg(feature = "oiio")]
+#[test]
+fn test_write_scanline() {
+    let image: Image<u8, Rgb> = Image::generate((64, 32), |u, v| Pixel::from(vec![u, v, 0.5]));
+    image.save("images/test-scanline0.png").unwrap();
+
+    let mut output = ImageOutput::create("images/test-scanline1.png").unwrap();
+    output.begin(&image.meta).unwrap();
+    for y in 0..image.height() {
+        output
+            .write_scanline::<u8, Rgb>(y, image.row(y).as_slice())
+            .unwrap();
+    }
+    output.finish().unwrap();
+
+    let a: Image<u8, Rgb> = Image::open("images/test-scanline0.png").unwrap();
+    let b: Image<u8, Rgb> = Image::open("images/test-scanline1.png").unwrap();
+    assert!(a == b);
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_encode_decode() {
+    let image: Image<u8, Rgb> = Image::generate((64, 32), |u, v| Pixel::from(vec![u, v, 0.5]));
+    let data = io::encode(&image, "png").unwrap();
+    assert!(!data.is_empty());
+    let image1: Image<u8, Rgb> = io::decode(&data, Some("png")).unwrap();
+    assert!(image == image1);
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_probe() {
+    let info = io::probe("images/A.exr").unwrap();
+    let image: Image<f32, Rgb> = Image::open("images/A.exr").unwrap();
+    assert!(info.width == image.width());
+    assert!(info.height == image.height());
+    assert!(info.subimages >= 1);
+    assert!(info.mip_levels >= 1);
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_open_with_meta() {
+    let (image, attrs): (Image<f32, Rgb>, _) = Image::open_with_meta("images/A.exr").unwrap();
+    assert!(image.width() > 0);
+    assert!(matches!(
+        attrs.get("compression"),
+        Some(io::oiio::AttrOwned::String(_))
+    ));
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_open_oriented() {
+    let mut image: Image<u8, Rgb> = Image::new((2, 3));
+    image.set((0, 0), [255, 0, 0]);
+
+    let mut output = io::oiio::ImageOutput::create("images/test-oriented.tif").unwrap();
+    output.spec_mut().set_attr("Orientation", 6);
+    output.write(&image).unwrap();
+
+    let oriented: Image<u8, Rgb> = Image::open_oriented("images/test-oriented.tif").unwrap();
+    assert!(oriented.size() == Size::new(3, 2));
+    assert!(oriented.get((2, 0)).as_slice() == [255, 0, 0]);
+
+    let plain: Image<u8, Rgb> = Image::open("images/test-oriented.tif").unwrap();
+    assert!(plain.size() == Size::new(2, 3));
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_read_all() {
+    let a: Image<u8, Rgb> = Image::generate((16, 8), |u, v| Pixel::from(vec![u, v, 0.0]));
+    let b: Image<u8, Rgb> = Image::generate((16, 8), |u, v| Pixel::from(vec![0.0, u, v]));
+
+    {
+        let mut output = ImageOutput::create("images/test-read-all.tif").unwrap();
+        output.append(&a).unwrap();
+        output.append(&b).unwrap();
+    }
+
+    let input = io::oiio::ImageInput::open("images/test-read-all.tif", None).unwrap();
+    assert!(input.num_subimages() == 2);
+    let images: Vec<Image<u8, Rgb>> = input.read_all().unwrap();
+    assert!(images.len() == 2);
+    assert!(images[0] == a);
+    assert!(images[1] == b);
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_write_quality() {
+    let image: Image<f32, Rgb> = Image::open("images/A.exr").unwrap();
+
+    let mut output = ImageOutput::create("images/test-quality-10.jpg").unwrap();
+    output.set_quality(10);
+    output.write(&image).unwrap();
+
+    let mut output = ImageOutput::create("images/test-quality-95.jpg").unwrap();
+    output.set_quality(95);
+    output.write(&image).unwrap();
+
+    let low = std::fs::metadata("images/test-quality-10.jpg")
+        .unwrap()
+        .len();
+    let high = std::fs::metadata("images/test-quality-95.jpg")
+        .unwrap()
+        .len();
+    assert!(low < high);
+}
+
+#[cfg(feature = "oiio")]
+#[test]
+fn test_read_region() {
+    let image: Image<f32, Rgb> = Image::generate((300, 200), |u, v| Pixel::from(vec![u, v, 0.5]));
+    image.save("images/test-read-region.exr").unwrap();
+
+    let roi = Region::new(Point::new(120, 50), Size::new(100, 100));
+    let input = io::oiio::ImageInput::open("images/test-read-region.exr", None).unwrap();
+    let region: Image<f32, Rgb> = input.read_region(roi).unwrap();
+    let full: Image<f32, Rgb> = input.read().unwrap();
+    assert!(region == full.crop(roi));
+
+    let outside = Region::new(Point::new(250, 150), Size::new(100, 100));
+    assert!(input.read_region::<f32, Rgb>(outside).is_err());
+}
+
 #[test]
 fn test_type_and_color_name() {
     assert!(f32::type_name() != f64::type_name());
@@ -265,6 +412,29 @@ fn test_text() {
     image.save("images/test-text.png").unwrap();
 }
 
+#[cfg(feature = "text")]
+#[test]
+fn test_draw_text_glyph() {
+    let mut image = Image::<f32, Rgb>::new((64, 64));
+    let font = include_bytes!("../images/OpenSans-Regular.ttf");
+    let font = text::font(font).unwrap();
+    let px = Pixel::from(vec![1.0, 1.0, 1.0]);
+    image.draw_text("I", &font, 32.0, (20, 40), &px);
+
+    let changed: Vec<_> = (0..64)
+        .flat_map(|y| (0..64).map(move |x| (x, y)))
+        .filter(|pt| image.get(*pt)[0] > 0.0)
+        .collect();
+    assert!(!changed.is_empty());
+
+    // The glyph sits above the baseline, starting near the left edge of `pos`
+    let width = text::width("I", &font, 32.0);
+    for (x, y) in changed {
+        assert!(y < 40 && y > 40 - 32);
+        assert!(x >= 20 && x < 20 + width);
+    }
+}
+
 #[cfg(feature = "mmap")]
 #[test]
 fn test_mmap() {
@@ -277,3 +447,1813 @@ fn test_mmap() {
     assert!(image == image1);
     image1.save("images/test-mmap.png").unwrap();
 }
+
+#[test]
+fn test_blend_mode() {
+    let mut a: Image<f32, Rgb> = Image::new((4, 4));
+    let mut b: Image<f32, Rgb> = Image::new((4, 4));
+    a.for_each(|_, mut px| {
+        px.as_slice_mut().copy_from_slice(&[0.25, 0.5, 0.75]);
+    });
+
+    b.for_each(|_, px| {
+        for x in px {
+            *x = 1.0;
+        }
+    });
+    let mut dest = a.new_like();
+    dest.apply(blend_mode(BlendMode::Multiply), &[&a, &b]);
+    assert!(dest == a);
+
+    b.for_each(|_, px| {
+        for x in px {
+            *x = 0.0;
+        }
+    });
+    dest.apply(blend_mode(BlendMode::Screen), &[&a, &b]);
+    assert!(dest == a);
+}
+
+#[test]
+fn test_hue_rotate() {
+    let mut image: Image<f32, Rgb> = Image::new((1, 1));
+    image.set_pixel((0, 0), &Pixel::from(vec![1.0, 0.0, 0.0]));
+    image.run_in_place(hue_rotate(120.));
+    let px = image.get_pixel((0, 0));
+    assert!(px[0].abs() < 1e-4);
+    assert!((px[1] - 1.0).abs() < 1e-4);
+    assert!(px[2].abs() < 1e-4);
+}
+
+#[test]
+fn test_pixel_saturating() {
+    let mut a = Pixel::<Rgb>::from(vec![0.75, 0.8, 0.9]);
+    let b = Pixel::<Rgb>::from(vec![0.5, 0.5, 0.5]);
+    a.saturating_add(&b);
+    assert!(a.iter().all(|x| *x == 1.0));
+
+    a.saturating_sub(&b).saturating_sub(&b).saturating_sub(&b);
+    assert!(a.iter().all(|x| *x == 0.0));
+}
+
+#[test]
+fn test_levels() {
+    let mut image: Image<f32, Rgb> = Image::new((8, 8));
+    image.for_each(|pt, px| {
+        for (c, x) in px.into_iter().enumerate() {
+            *x = ((pt.x + pt.y * 8) as f32 + c as f32) / 70.0;
+        }
+    });
+
+    let dest: Image<f32, Rgb> = image.run(levels(0., 1., 1., 0., 1.), None);
+    assert!(dest == image);
+
+    let a: Image<f32, Rgb> = image.run(levels(0., 1., 2.2, 0., 1.), None);
+    let b: Image<f32, Rgb> = image.run(gamma_lin(None), None);
+    assert!(a == b);
+
+    let step: Image<f32, Rgb> = image.run(levels(0.5, 0.5, 1., 0., 1.), None);
+    image.each_pixel(|pt, px| {
+        for (c, x) in px.iter().enumerate() {
+            let expected = if *x >= 0.5 { 1.0 } else { 0.0 };
+            assert!(step.get(pt)[c] as f64 == expected);
+        }
+    });
+}
+
+#[test]
+fn test_generate() {
+    let red = Pixel::<Rgb>::from(vec![1.0, 0.0, 0.0]);
+    let image: Image<f32, Rgb> = Image::generate((64, 16), |u, _| &red * u);
+    assert!(image.get_pixel((0, 8)) == Pixel::from(vec![0.0, 0.0, 0.0]));
+    assert!(image.get_pixel((63, 8)) == red);
+}
+
+#[test]
+fn test_channel_map() {
+    let mut image: Image<u8, Rgb> = Image::new((2, 2));
+    image.set((1, 1), [10, 20, 30]);
+    let dest: Image<u8, Rgb> = image.run(channel_map(&[2, 1, 0]).unwrap(), None);
+    assert!(dest.get((1, 1)).as_slice() == [30, 20, 10]);
+
+    assert!(channel_map::<u8, Rgb, u8, Rgb>(&[0, 1, 5]).is_err());
+    assert!(channel_map::<u8, Rgb, u8, Rgb>(&[0, 1]).is_err());
+}
+
+#[test]
+fn test_rotate_about() {
+    let mut image: Image<f32, Rgb> = Image::new((8, 8));
+    image.set((0, 0), [1.0, 0.0, 0.0]);
+    image.set((3, 0), [0.0, 1.0, 0.0]);
+
+    let fill = Pixel::from(vec![0.0, 0.0, 1.0]);
+    let dest = image.rotate_about(90., (0, 0), fill.clone());
+    assert!(dest.get_pixel((0, 0)) == image.get_pixel((0, 0)));
+
+    let px = dest.get_pixel((0, 3));
+    assert!((px[1] - 1.0).abs() < 1e-6);
+    assert!(dest.get_pixel((3, 0)) == fill);
+}
+
+#[test]
+fn test_extract_channel() {
+    let image: Image<u16, Rgb> =
+        Image::generate((16, 16), |u, v| Pixel::from(vec![u, v, (u + v) / 2.]));
+    let g = image.extract_channel(1).unwrap();
+    image.each_pixel(|pt, px| {
+        assert!(g.get(pt)[0] == u16::from_norm(px[1]));
+    });
+    assert!(image.extract_channel(3).is_err());
+}
+
+#[test]
+fn test_to_display() {
+    let image: Image<f32, Rgb> =
+        Image::generate((32, 32), |u, v| Pixel::from(vec![u * 4.0, v * 0.5, 0.05]));
+    let display = image.to_display();
+    let mut sum = 0.0;
+    display.each_pixel(|_, px| {
+        assert!(px.iter().all(|x| (0.0..=1.0).contains(x)));
+        sum += px.iter().sum::<f64>() / 3.0;
+    });
+    let mean = sum / (32.0 * 32.0);
+    assert!(mean > 0.2 && mean < 0.8);
+}
+
+#[test]
+fn test_premultiply_alpha() {
+    let image: Image<f32, Rgba> =
+        Image::generate((16, 16), |u, v| Pixel::from(vec![u, v, 0.5, (u + v) / 2.]));
+    let mut tmp = image.clone();
+    tmp.premultiply_alpha();
+    assert!(tmp != image);
+    tmp.unpremultiply_alpha();
+    image.each_pixel(|pt, px| {
+        let a = tmp.get_pixel(pt);
+        if px[3] > 0.0 {
+            for i in 0..4 {
+                assert!((a[i] - px[i]).abs() < 1e-5);
+            }
+        }
+    });
+
+    let rgb: Image<f32, Rgb> = Image::generate((16, 16), |u, v| Pixel::from(vec![u, v, 0.5]));
+    let mut tmp = rgb.clone();
+    tmp.premultiply_alpha();
+    assert!(tmp == rgb);
+}
+
+#[test]
+fn test_lab() {
+    for rgb in [
+        [1.0, 1.0, 1.0],
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.2, 0.6, 0.4],
+        [0.9, 0.5, 0.1],
+    ] {
+        let px = Pixel::<Rgb>::from(rgb.to_vec());
+        let lab: Pixel<Lab> = px.convert();
+        let back: Pixel<Rgb> = lab.convert();
+        for i in 0..3 {
+            assert!((px[i] - back[i]).abs() < 1e-3);
+        }
+    }
+
+    let white: Pixel<Lab> = Pixel::<Rgb>::from(vec![1.0, 1.0, 1.0]).convert();
+    assert!((white[0] - 100.0).abs() < 0.1);
+    assert!(white[1].abs() < 0.1);
+    assert!(white[2].abs() < 0.1);
+}
+
+#[test]
+fn test_hsl() {
+    for rgb in [
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.2, 0.6, 0.4],
+        [0.9, 0.5, 0.1],
+        [0.7, 0.2, 0.8],
+    ] {
+        let px = Pixel::<Rgb>::from(rgb.to_vec());
+        let hsl: Pixel<Hsl> = px.convert();
+        assert!(hsl[0] >= 0.0 && hsl[0] < 1.0);
+        let back: Pixel<Rgb> = hsl.convert();
+        for i in 0..3 {
+            assert!((px[i] - back[i]).abs() < 1e-6);
+        }
+    }
+
+    let gray: Pixel<Hsl> = Pixel::<Rgb>::from(vec![0.5, 0.5, 0.5]).convert();
+    assert!(gray[1] == 0.0);
+    assert!(gray[2] == 0.5);
+}
+
+#[test]
+fn test_apply_boxed() {
+    let image: Image<f32, Rgb> = Image::generate((16, 16), |u, v| Pixel::from(vec![u, v, 0.25]));
+    let f: Box<dyn Filter<f32, Rgb>> = Box::new(invert());
+    let mut dest = image.new_like();
+    dest.apply_boxed(f.as_ref(), &[&image]);
+    image.each_pixel(|pt, px| {
+        let inv = dest.get_pixel(pt);
+        for i in 0..3 {
+            assert!((inv[i] - (1.0 - px[i])).abs() < 1e-6);
+        }
+    });
+}
+
+#[test]
+fn test_delta_e() {
+    let a: Image<f32, Rgb> = Image::new((8, 8));
+    let b = a.clone();
+    let diff = a.delta_e_map(&b).unwrap();
+    assert!(diff.data().iter().all(|x| *x == 0.0));
+    assert!(a.delta_e_map(&Image::new((4, 8))).is_err());
+
+    let x = Pixel::<Lab>::from(vec![50.0, 2.6772, -79.7751]);
+    let y = Pixel::<Lab>::from(vec![50.0, 0.0, -82.7485]);
+    assert!((x.delta_e(&y) - 4.0011).abs() < 1e-3);
+    assert!((x.delta_e94(&y) - 1.3950).abs() < 1e-3);
+    assert!(x.delta_e(&x) == 0.0);
+}
+
+#[test]
+fn test_on_checkerboard() {
+    let mut image: Image<f32, Rgba> = Image::new((16, 16));
+    image.set((3, 3), [1.0, 0.0, 0.0, 1.0]);
+    let preview = image.on_checkerboard(4);
+    assert!(preview.get((3, 3)).as_slice() == [1.0, 0.0, 0.0]);
+    assert!(preview.get((0, 0)).as_slice() == [1.0, 1.0, 1.0]);
+    assert!(preview.get((4, 0)).as_slice() == [0.8, 0.8, 0.8]);
+    assert!(preview.get((4, 4)).as_slice() == [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_grayscale_weights() {
+    let image: Image<f32, Rgb> = Image::generate((16, 16), |u, v| Pixel::from(vec![u, v, 0.3]));
+
+    let avg: Image<f32, Gray> = image.run(grayscale([1.0, 1.0, 1.0]), None);
+    let avg1: Image<f32, Gray> = image.run(grayscale([1. / 3., 1. / 3., 1. / 3.]), None);
+    image.each_pixel(|pt, px| {
+        let mean = (px[0] + px[1] + px[2]) / 3.0;
+        assert!((avg.get(pt)[0] as f64 - mean).abs() < 1e-6);
+        assert!((avg1.get(pt)[0] as f64 - mean).abs() < 1e-6);
+    });
+
+    let a: Image<f32, Gray> = image.run(grayscale([0.299, 0.587, 0.114]), None);
+    let b: Image<f32, Gray> = image.run(grayscale([2.99, 5.87, 1.14]), None);
+    assert!(a == b);
+}
+
+#[test]
+fn test_rgb_to_gray() {
+    let blue: Pixel<Gray> = Pixel::<Rgb>::from(vec![0.0, 0.0, 1.0]).convert();
+    assert!((blue[0] - 0.07).abs() < 1e-9);
+
+    let white: Pixel<Gray> = Pixel::<Rgb>::from(vec![1.0, 1.0, 1.0]).convert();
+    assert!((white[0] - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_multi() {
+    let mut image: Image<f32, Multi<5>> = Image::new((4, 4));
+    assert!(image.channels() == 5);
+
+    let px = Pixel::<Multi<5>>::from(vec![0.1, 0.2, 0.3, 0.4, 0.5]);
+    image.set_pixel((2, 3), &px);
+    let px1 = image.get_pixel((2, 3));
+    assert!(px1.len() == 5);
+    for i in 0..5 {
+        assert!((px[i] - px1[i]).abs() < 1e-6);
+    }
+
+    let rgb: Pixel<Rgb> = px1.convert();
+    assert!((rgb[2] - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn test_pyramid_mean_downscale() {
+    let mut image: Image<u8, Rgb> = Image::new((64, 64));
+    image.data_mut().iter_mut().for_each(|x| *x = 128);
+
+    for _ in 0..4 {
+        image = image.pyramid_mean_downscale();
+    }
+
+    assert!(image.size() == Size::new(4, 4));
+    assert!(image.data().iter().all(|x| (127..=129).contains(x)));
+
+    let empty: Image<f32, Rgb> = Image::new((0, 6));
+    assert!(empty.pyramid_mean_downscale().size() == Size::new(0, 3));
+    let empty: Image<f32, Rgb> = Image::new((6, 0));
+    assert!(empty.pyramid_mean_downscale().size() == Size::new(3, 0));
+    let empty: Image<f32, Rgb> = Image::new((0, 0));
+    assert!(empty.pyramid_mean_downscale().size() == Size::new(0, 0));
+}
+
+#[test]
+fn test_rank_filter() {
+    let image: Image<f32, Gray> = Image::generate((8, 8), |u, v| {
+        Pixel::from(vec![((u * 7.0 * 13.0 + v * 7.0 * 5.0) % 8.0) / 8.0])
+    });
+
+    let neighborhood = |x: usize, y: usize| {
+        let mut values = Vec::new();
+        for j in y.saturating_sub(1)..=y + 1 {
+            for i in x.saturating_sub(1)..=x + 1 {
+                if image.in_bounds((i, j)) {
+                    values.push(image.get((i, j))[0]);
+                }
+            }
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values
+    };
+
+    let min = image.min_filter(1);
+    let max = image.max_filter(1);
+    let median: Image<f32, Gray> = image.run(rank_filter(1, 4), None);
+    for y in 0..8 {
+        for x in 0..8 {
+            let values = neighborhood(x, y);
+            assert!(min.get((x, y))[0] == values[0]);
+            assert!(max.get((x, y))[0] == values[values.len() - 1]);
+            if values.len() == 9 {
+                assert!(median.get((x, y))[0] == values[4]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_apply_mask() {
+    let mut image: Image<u8, Rgb> = Image::new((10, 10));
+    image.for_each(|_, px| {
+        for x in px {
+            *x = 255;
+        }
+    });
+
+    let mut mask: Image<u8, Gray> = Image::new((10, 10));
+    mask.for_each(|pt, mut px| {
+        if pt.x < 5 {
+            px[0] = 255;
+        }
+    });
+
+    let mut a = image.clone();
+    a.apply_mask(&mask, false).unwrap();
+    let zeroed = a
+        .iter()
+        .filter(|(_, px)| px.as_slice().iter().all(|x| *x == 0))
+        .count();
+    assert!(zeroed == 50);
+
+    image.apply_mask(&mask, true).unwrap();
+    let zeroed = image
+        .iter()
+        .filter(|(pt, px)| pt.x < 5 && px.as_slice().iter().all(|x| *x == 0))
+        .count();
+    assert!(zeroed == 50);
+
+    let small: Image<u8, Gray> = Image::new((5, 5));
+    assert!(a.apply_mask(&small, false).is_err());
+}
+
+#[test]
+fn test_bounding_boxes() {
+    let mut labels: Image<u32, Gray> = Image::new((10, 10));
+    labels.for_each(|pt, mut px| {
+        if pt.x >= 1 && pt.x <= 3 && pt.y >= 2 && pt.y <= 5 {
+            px[0] = 1;
+        } else if pt.x >= 6 && pt.y == 8 {
+            px[0] = 2;
+        }
+    });
+
+    let boxes = labels.bounding_boxes();
+    assert!(boxes.len() == 2);
+    assert!(boxes[&1] == Region::new(Point::new(1, 2), Size::new(3, 4)));
+    assert!(boxes[&2] == Region::new(Point::new(6, 8), Size::new(4, 1)));
+
+    assert!(Image::<u32, Gray>::new((0, 4)).bounding_boxes().is_empty());
+    assert!(Image::<u32, Gray>::new((4, 0)).bounding_boxes().is_empty());
+}
+
+#[test]
+fn test_orient() {
+    let mut image: Image<u8, Gray> = Image::new((2, 3));
+    image.set((0, 0), [1]);
+    image.set((1, 2), [2]);
+
+    let a = image.orient(6);
+    assert!(a.size() == Size::new(3, 2));
+    assert!(a.get((2, 0))[0] == 1);
+    assert!(a.get((0, 1))[0] == 2);
+
+    let b = image.orient(8);
+    assert!(b.size() == Size::new(3, 2));
+    assert!(b.get((0, 1))[0] == 1);
+    assert!(b.get((2, 0))[0] == 2);
+
+    assert!(image.orient(3).get((1, 2))[0] == 1);
+    assert!(image.orient(5).get((0, 0))[0] == 1);
+    assert!(image.orient(7).get((0, 0))[0] == 2);
+    assert!(image.orient(1) == image);
+    assert!(image.rotate90().rotate270() == image);
+    assert!(image.flip_horizontal().flip_horizontal() == image);
+}
+
+#[test]
+fn test_histogram_gamma() {
+    let image = Image::<f32, Gray>::generate((256, 1), |x, _| Pixel::from(vec![x.powf(2.2)]));
+
+    let spread = |h: &Histogram| {
+        let d = h.distribution();
+        let max = d.iter().cloned().fold(0.0, f64::max);
+        let min = d.iter().cloned().fold(1.0, f64::min);
+        max - min
+    };
+
+    let linear = image.histogram(16);
+    let perceptual = image.histogram_gamma(16, 1.0 / 2.2);
+    assert!(perceptual[0].sum() == linear[0].sum());
+    assert!(spread(&perceptual[0]) < spread(&linear[0]));
+}
+
+#[test]
+fn test_dominant_colors() {
+    let colors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let mut image: Image<f32, Rgb> = Image::new((30, 10));
+    image.for_each(|pt, mut px| {
+        px.copy_from_slice(colors[pt.x / 10]);
+    });
+
+    let dominant = image.dominant_colors(3, 10);
+    assert!(dominant.len() == 3);
+    for color in colors.iter() {
+        let found = dominant.iter().any(|(px, weight)| {
+            (*weight - 1.0 / 3.0).abs() < 1e-6
+                && (0..3).all(|c| (px[c] - color[c] as f64).abs() < 1e-3)
+        });
+        assert!(found);
+    }
+}
+
+#[test]
+fn test_scale_nearest() {
+    let mut image: Image<u8, Gray> = Image::new((2, 2));
+    image.set((0, 0), [1]);
+    image.set((1, 0), [2]);
+    image.set((0, 1), [3]);
+    image.set((1, 1), [4]);
+
+    let scaled = image.scale_nearest(3);
+    assert!(scaled.size() == Size::new(6, 6));
+    for y in 0..6 {
+        for x in 0..6 {
+            assert!(scaled.get((x, y))[0] == image.get((x / 3, y / 3))[0]);
+        }
+    }
+    assert!(scaled.get((2, 2))[0] == 1);
+    assert!(scaled.get((3, 2))[0] == 2);
+    assert!(scaled.get((2, 3))[0] == 3);
+    assert!(scaled.get((5, 5))[0] == 4);
+}
+
+#[cfg(feature = "ffmpeg")]
+#[test]
+fn test_video_reader() {
+    if std::process::Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
+
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-v",
+            "error",
+            "-y",
+            "-f",
+            "lavfi",
+            "-i",
+            "testsrc=size=64x48:rate=10",
+            "-frames:v",
+            "10",
+            "-c:v",
+            "ffv1",
+            "images/test-video.mkv",
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let video = io::ffmpeg::VideoReader::open("images/test-video.mkv").unwrap();
+    assert!(video.width() == 64);
+    assert!(video.height() == 48);
+    assert!((video.fps() - 10.0).abs() < 1e-6);
+
+    let mut count = 0;
+    for frame in video {
+        assert!(frame.size() == Size::new(64, 48));
+        count += 1;
+    }
+    assert!(count == 10);
+
+    assert!(io::ffmpeg::VideoReader::open("images/does-not-exist.mkv").is_err());
+}
+
+#[test]
+fn test_run_output_size() {
+    let image: Image<f32, Rgb> = Image::new((20, 10));
+    let roi = Region::new(Point::new(2, 3), Size::new(5, 4));
+    let cropped: Image<f32, Rgb> = image.run(crop(roi), None);
+    assert!(cropped.size() == roi.size);
+
+    let same = image.run(noop::<f32, Rgb, f32, Rgb>(), None);
+    assert!(same.size() == image.size());
+}
+
+#[cfg(feature = "ffmpeg")]
+#[test]
+fn test_video_writer() {
+    if std::process::Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
+
+    let mut writer =
+        io::ffmpeg::VideoWriter::create("images/test-video-writer.mkv", 32, 16, 5.0).unwrap();
+    let mut frame: Image<u8, Rgb> = Image::new((32, 16));
+    frame.for_each(|_, mut px| px.copy_from_slice([255, 0, 0]));
+    for _ in 0..10 {
+        writer.write_frame(&frame).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let frames: Vec<Image<u8, Rgb>> = io::ffmpeg::VideoReader::open("images/test-video-writer.mkv")
+        .unwrap()
+        .collect();
+    assert!(frames.len() == 10);
+    let px = frames[5].get((10, 10));
+    assert!(px[0] > 200 && px[1] < 50 && px[2] < 50);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_color_name() {
+    let meta = Meta::<f32, Rgb>::new((4, 4));
+    Mmap::create_image("images/test-color-name.image2", &meta).unwrap();
+
+    let image = Mmap::<f32>::load_image::<Rgb>("images/test-color-name.image2");
+    assert!(image.is_ok());
+
+    let image = Mmap::<f32>::load_image::<Hsv>("images/test-color-name.image2");
+    assert!(matches!(image, Err(Error::InvalidType)));
+
+    let mut old = b"img2".to_vec();
+    old.extend_from_slice(&[0u8; 64]);
+    std::fs::write("images/test-color-name-old.image2", old).unwrap();
+    let image = Mmap::<f32>::load_image::<Rgb>("images/test-color-name-old.image2");
+    assert!(image.is_err());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_readonly() {
+    let mut image: Image<f32, Rgb> = Image::new((8, 8));
+    image.set((3, 4), [0.25, 0.5, 0.75]);
+    image.mmap_clone("images/test-readonly.image2").unwrap();
+
+    let image2 = Mmap::<f32>::load_image_readonly::<Rgb>("images/test-readonly.image2").unwrap();
+    assert!(image2.get((3, 4)).unwrap().as_slice() == [0.25, 0.5, 0.75]);
+    assert!(image2.get((8, 0)).is_none());
+    assert!(image2.data() == image.data());
+
+    let mut copy = image2.to_image();
+    assert!(copy == image);
+    copy.set((0, 0), [1.0, 1.0, 1.0]);
+    assert!(image2.get((0, 0)).unwrap().as_slice() == [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_statistics() {
+    let image = Image::<f32, Gray>::generate((101, 4), |x, _| Pixel::from(vec![x]));
+    let stats = image.statistics();
+    assert!(stats.len() == 1);
+    assert!(stats[0].min == 0.0);
+    assert!(stats[0].max == 1.0);
+    assert!((stats[0].mean - 0.5).abs() < 1e-6);
+    assert!((stats[0].stddev - 0.2915).abs() < 1e-3);
+}
+
+#[test]
+fn test_resize_with_alpha() {
+    let image = Image::<u8, Rgba>::generate((8, 8), |_, _| Pixel::from(vec![1.0, 0.5, 0.0, 0.5]));
+    let alpha = image.get((0, 0))[3];
+    for mode in [
+        InterpolationMode::Nearest,
+        InterpolationMode::Bilinear,
+        InterpolationMode::Bicubic,
+    ] {
+        let small = image.resize_with((3, 5), mode);
+        let large = image.resize_with((13, 11), mode);
+        for img in [small, large] {
+            assert!(img
+                .data()
+                .chunks(4)
+                .all(|px| (px[3] as i32 - alpha as i32).abs() <= 1));
+        }
+    }
+}
+
+#[test]
+fn test_resize_with() {
+    let mut image: Image<f32, Gray> = Image::new((64, 64));
+    image.for_each(|pt, mut px| {
+        px[0] = ((pt.x + pt.y) % 2) as f32;
+    });
+
+    let small = image.resize_with((16, 16), InterpolationMode::Bilinear);
+    assert!(small.size() == Size::new(16, 16));
+    assert!(small.data().iter().all(|x| (x - 0.5).abs() < 1e-6));
+
+    let nearest = image.resize_with((16, 16), InterpolationMode::Nearest);
+    assert!(nearest.data().iter().all(|x| *x == 0.0 || *x == 1.0));
+
+    let gradient = Image::<f32, Gray>::generate((4, 4), |x, _| Pixel::from(vec![x]));
+    for mode in [InterpolationMode::Bilinear, InterpolationMode::Bicubic] {
+        let large = gradient.resize_with((16, 16), mode);
+        assert!(large.size() == Size::new(16, 16));
+        for y in 0..16 {
+            for x in 1..16 {
+                assert!(large.get((x, y))[0] >= large.get((x - 1, y))[0] - 1e-6);
+            }
+        }
+    }
+
+    let empty = Image::<f32, Gray>::new((0, 0));
+    for mode in [
+        InterpolationMode::Nearest,
+        InterpolationMode::Bilinear,
+        InterpolationMode::Bicubic,
+    ] {
+        let out = empty.resize_with((4, 4), mode);
+        assert!(out.size() == Size::new(4, 4));
+        assert!(out.data().iter().all(|x| *x == 0.0));
+        assert!(
+            Image::<f32, Gray>::new((0, 3))
+                .resize_with((2, 2), mode)
+                .size()
+                == Size::new(2, 2)
+        );
+    }
+}
+
+#[test]
+fn test_generate_mipmaps() {
+    let image = Image::<f32, Rgb>::generate((8, 8), |x, y| Pixel::from(vec![x, y, 0.5]));
+    let levels = image.generate_mipmaps();
+    let sizes: Vec<usize> = levels.iter().map(|i| i.width()).collect();
+    assert!(sizes == [8, 4, 2, 1]);
+    assert!(levels.iter().all(|i| i.width() == i.height()));
+    assert!(levels[0] == image);
+
+    let odd: Image<u8, Gray> = Image::new((5, 3));
+    let levels = odd.generate_mipmaps();
+    let sizes: Vec<(usize, usize)> = levels.iter().map(|i| (i.width(), i.height())).collect();
+    assert!(sizes == [(5, 3), (2, 1), (1, 1)]);
+}
+
+#[test]
+fn test_view() {
+    let image = Image::<u8, Rgb>::generate((32, 24), |x, y| Pixel::from(vec![x, y, x * y]));
+    let regions = [
+        Region::new(Point::new(0, 0), Size::new(32, 24)),
+        Region::new(Point::new(3, 5), Size::new(10, 7)),
+        Region::new(Point::new(31, 23), Size::new(1, 1)),
+        Region::new(Point::new(16, 0), Size::new(16, 24)),
+    ];
+
+    for roi in regions {
+        let view = image.view(roi);
+        let crop = image.crop(roi);
+        assert!(view.size() == crop.size());
+        for (pt, data) in view.iter() {
+            assert!(data.as_slice() == crop.get(pt).as_slice());
+            assert!(view.get_pixel(pt) == crop.get_pixel(pt));
+        }
+        assert!(view.iter().count() == roi.area());
+        assert!(view.to_image() == crop);
+    }
+
+    let clamped = image.view(Region::new(Point::new(30, 20), Size::new(10, 10)));
+    assert!(clamped.size() == Size::new(2, 4));
+    assert!(clamped.checked_get_pixel((1, 3)) == Some(image.get_pixel((31, 23))));
+    assert!(clamped.checked_get((2, 0)).is_none());
+    assert!(clamped.checked_get_pixel((0, 4)).is_none());
+    assert!(format!("{:?}", clamped).starts_with("ImageView"));
+}
+
+#[test]
+#[should_panic(expected = "outside of the view")]
+fn test_view_get_out_of_bounds() {
+    let image = Image::<u8, Gray>::new((8, 8));
+    let view = image.view(Region::new(Point::new(2, 2), Size::new(4, 4)));
+    view.get((4, 0));
+}
+
+#[test]
+#[should_panic(expected = "outside of the view")]
+fn test_view_row_out_of_bounds() {
+    let image = Image::<u8, Gray>::new((8, 8));
+    let view = image.view(Region::new(Point::new(2, 2), Size::new(4, 4)));
+    view.row(4);
+}
+
+#[test]
+fn test_pad() {
+    let mut image: Image<u8, Gray> = Image::new((2, 2));
+    image.set((0, 0), [1]);
+    image.set((1, 0), [2]);
+    image.set((0, 1), [3]);
+    image.set((1, 1), [4]);
+
+    let fill = Pixel::from(vec![1.0]);
+    let padded = image.pad(1, 1, 1, 1, &fill);
+    assert!(padded.size() == Size::new(4, 4));
+    for y in 0..4 {
+        for x in 0..4 {
+            let v = padded.get((x, y))[0];
+            if (1..3).contains(&x) && (1..3).contains(&y) {
+                assert!(v == image.get((x - 1, y - 1))[0]);
+            } else {
+                assert!(v == 255);
+            }
+        }
+    }
+
+    let row = |img: &Image<u8, Gray>, y: usize| -> Vec<u8> { img.row(y).as_slice().to_vec() };
+    let mut wide: Image<u8, Gray> = Image::new((3, 1));
+    wide.data_mut().copy_from_slice(&[1, 2, 3]);
+    assert!(row(&wide.pad_with(0, 0, 2, 2, BorderMode::Replicate), 0) == [1, 1, 1, 2, 3, 3, 3]);
+    assert!(row(&wide.pad_with(0, 0, 2, 2, BorderMode::Wrap), 0) == [2, 3, 1, 2, 3, 1, 2]);
+    assert!(row(&wide.pad_with(0, 0, 2, 2, BorderMode::Reflect), 0) == [3, 2, 1, 2, 3, 2, 1]);
+
+    let empty: Image<u8, Gray> = Image::new((0, 0));
+    for mode in [BorderMode::Replicate, BorderMode::Wrap, BorderMode::Reflect] {
+        let padded = empty.pad_with(1, 2, 3, 4, mode);
+        assert!(padded.size() == Size::new(7, 3));
+        assert!(padded.data().iter().all(|x| *x == 0));
+    }
+}
+
+#[test]
+fn test_concat() {
+    let mut a: Image<u8, Gray> = Image::new((2, 2));
+    a.data_mut().copy_from_slice(&[1, 2, 3, 4]);
+    let mut b: Image<u8, Gray> = Image::new((2, 2));
+    b.data_mut().copy_from_slice(&[5, 6, 7, 8]);
+
+    let h = Image::hconcat(&[&a, &b]).unwrap();
+    assert!(h.size() == Size::new(4, 2));
+    assert!(h.data() == [1, 2, 5, 6, 3, 4, 7, 8]);
+    assert!(h.crop(Region::new(Point::new(0, 0), Size::new(2, 2))) == a);
+    assert!(h.crop(Region::new(Point::new(2, 0), Size::new(2, 2))) == b);
+
+    let v = Image::vconcat(&[&a, &b]).unwrap();
+    assert!(v.size() == Size::new(2, 4));
+    assert!(v.data() == [1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let c: Image<u8, Gray> = Image::new((3, 3));
+    assert!(Image::hconcat(&[&a, &c]).is_err());
+    assert!(Image::vconcat(&[&a, &c]).is_err());
+    assert!(Image::<u8, Gray>::hconcat(&[]).is_err());
+}
+
+#[test]
+fn test_tiles() {
+    let image = Image::<u16, Rgb>::generate((10, 10), |x, y| Pixel::from(vec![x, y, 1.0]));
+    let tiles = image.split_tiles((3, 3));
+    assert!(tiles.len() == 16);
+    assert!(tiles[3].0 == Point::new(9, 0));
+    assert!(tiles[3].1.size() == Size::new(1, 3));
+    assert!(tiles[15].1.size() == Size::new(1, 1));
+
+    let joined = Image::from_tiles(image.size(), &tiles);
+    assert!(joined == image);
+}
+
+#[test]
+fn test_perceptual_hash() {
+    let a = Image::<f32, Rgb>::generate((128, 96), |x, y| {
+        let v = ((x * 7.0).sin() * (y * 5.0).cos() + 1.0) / 2.0;
+        Pixel::from(vec![v, v * x, v * y])
+    });
+    let mut blurred = a.new_like();
+    Kernel::gaussian_5x5().eval(&[&a], &mut blurred);
+    let scaled = a.resize_with((64, 48), InterpolationMode::Bilinear);
+    let other = Image::<f32, Rgb>::generate((128, 96), |x, y| {
+        let v = ((x * 23.0).cos() * (y * 17.0).sin() + 1.0) / 2.0;
+        Pixel::from(vec![1.0 - v, v * y, v])
+    });
+
+    assert!(a.ahash() == a.clone().ahash());
+    for hash in [Image::ahash, Image::dhash, Image::phash] {
+        let h = hash(&a);
+        assert!(h.hamming_distance(&hash(&blurred)) <= 8);
+        assert!(h.hamming_distance(&hash(&scaled)) <= 8);
+        assert!(h.hamming_distance(&hash(&other)) >= 16);
+    }
+}
+
+#[test]
+fn test_integral() {
+    let mut constant: Image<f32, Rgb> = Image::new((10, 8));
+    constant.for_each(|_, mut px| px.copy_from_slice([0.5, 0.25, 1.0]));
+    let integral = constant.integral();
+    assert!(integral.size() == Size::new(11, 9));
+
+    let roi = Region::new(Point::new(2, 3), Size::new(4, 5));
+    let sum = integral.integral_sum(roi);
+    assert!((sum[0] - 20.0 * 0.5).abs() < 1e-9);
+    assert!((sum[1] - 20.0 * 0.25).abs() < 1e-9);
+    assert!((sum[2] - 20.0).abs() < 1e-9);
+
+    let image = Image::<u8, Gray>::generate((13, 7), |x, y| Pixel::from(vec![x * y]));
+    let integral = image.integral();
+    for roi in [
+        Region::new(Point::new(0, 0), Size::new(13, 7)),
+        Region::new(Point::new(5, 2), Size::new(3, 4)),
+        Region::new(Point::new(12, 6), Size::new(1, 1)),
+    ] {
+        let mut expected = 0.0;
+        for y in roi.min_y()..roi.max_y() {
+            for x in roi.min_x()..roi.max_x() {
+                expected += image.get((x, y))[0].to_norm();
+            }
+        }
+        assert!((integral.integral_sum(roi)[0] - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_connected_components() {
+    let mut mask: Image<u8, Gray> = Image::new((12, 8));
+    mask.for_each(|pt, mut px| {
+        let a = (1..4).contains(&pt.x) && (1..4).contains(&pt.y);
+        let b = (6..10).contains(&pt.x) && (3..7).contains(&pt.y);
+        if a || b {
+            px[0] = 255;
+        }
+    });
+
+    let (labels, count) = mask.connected_components(0.5, Connectivity::Four);
+    assert!(count == 2);
+    let a = labels.get((1, 1))[0];
+    let b = labels.get((6, 3))[0];
+    assert!(a != 0 && b != 0 && a != b);
+    for y in 0..8 {
+        for x in 0..12 {
+            let expected = if mask.get((x, y))[0] == 0 {
+                0
+            } else if x < 5 {
+                a
+            } else {
+                b
+            };
+            assert!(labels.get((x, y))[0] == expected);
+        }
+    }
+
+    let mut diagonal: Image<u8, Gray> = Image::new((3, 3));
+    diagonal.set((0, 0), [255]);
+    diagonal.set((1, 1), [255]);
+    diagonal.set((2, 0), [255]);
+    assert!(diagonal.connected_components(0.5, Connectivity::Four).1 == 3);
+    assert!(diagonal.connected_components(0.5, Connectivity::Eight).1 == 1);
+}
+
+#[test]
+fn test_bounding_box() {
+    let white = Pixel::from(vec![1.0, 1.0, 1.0]);
+    let mut image: Image<u8, Rgb> = Image::new((20, 15));
+    image.for_each(|pt, mut px| {
+        if (4..9).contains(&pt.x) && (6..8).contains(&pt.y) {
+            px.copy_from_slice([200, 10, 10]);
+        } else {
+            px.copy_from_slice([255, 255, 255]);
+        }
+    });
+
+    let roi = image.bounding_box(&white, 0.01).unwrap();
+    assert!(roi == Region::new(Point::new(4, 6), Size::new(5, 2)));
+
+    let mut uniform: Image<u8, Rgb> = Image::new((4, 4));
+    uniform.for_each(|_, mut px| px.copy_from_slice([255, 255, 255]));
+    assert!(uniform.bounding_box(&white, 0.01).is_none());
+}
+
+#[test]
+fn test_blend_weighted() {
+    let a = Pixel::<Rgba>::from(vec![0.0, 0.2, 1.0, 1.0]);
+    let b = Pixel::<Rgba>::from(vec![1.0, 0.6, 0.0, 0.0]);
+    assert!(a.lerp(&b, 0.0) == a);
+    assert!(a.lerp(&b, 1.0) == b);
+    let mid = a.lerp(&b, 0.5);
+    for (c, expected) in [0.5, 0.4, 0.5, 0.5].iter().enumerate() {
+        assert!((mid[c] - expected).abs() < 1e-9);
+    }
+
+    let mut x: Image<f32, Rgb> = Image::new((4, 4));
+    x.for_each(|_, mut px| px.copy_from_slice([0.0, 0.5, 1.0]));
+    let mut y: Image<f32, Rgb> = Image::new((4, 4));
+    y.for_each(|_, mut px| px.copy_from_slice([1.0, 0.5, 0.0]));
+
+    assert!(x.blend_weighted(&y, 0.0) == x);
+    assert!(x.blend_weighted(&y, 1.0) == y);
+    let mid = x.blend_weighted(&y, 0.5);
+    assert!(mid.data().iter().all(|v| (v - 0.5).abs() < 1e-6));
+}
+
+#[test]
+fn test_pixel_distance() {
+    let a = Pixel::<Rgb>::from(vec![0.0, 0.0, 0.0]);
+    let b = Pixel::<Rgb>::from(vec![0.3, 0.4, 0.0]);
+    assert!((a.distance(&b, DistanceMetric::Euclidean) - 0.5).abs() < 1e-9);
+    assert!((a.distance(&b, DistanceMetric::Manhattan) - 0.7).abs() < 1e-9);
+    assert!(b.distance(&b, DistanceMetric::Euclidean) == 0.0);
+    assert!(b.distance(&b, DistanceMetric::Manhattan) == 0.0);
+
+    let x = Pixel::<Rgba>::from(vec![0.1, 0.2, 0.3, 0.0]);
+    let y = Pixel::<Rgba>::from(vec![0.1, 0.2, 0.3, 1.0]);
+    assert!(x.distance(&y, DistanceMetric::Euclidean) == 0.0);
+    assert!(x.distance(&y, DistanceMetric::Manhattan) == 0.0);
+}
+
+#[test]
+fn test_chroma_key() {
+    let mut image: Image<f32, Rgba> = Image::new((10, 10));
+    image.for_each(|pt, mut px| {
+        if pt.x < 5 {
+            px.copy_from_slice([0.05, 0.95, 0.1, 1.0]);
+        } else {
+            px.copy_from_slice([0.8, 0.3, 0.2, 1.0]);
+        }
+    });
+
+    let key = Pixel::from(vec![0.0, 1.0, 0.0]);
+    let dest: Image<f32, Rgba> = image.run(chroma_key(key.clone(), 0.2, 0.1), None);
+    for y in 0..10 {
+        for x in 0..10 {
+            let expected = if x < 5 { 0.0 } else { 1.0 };
+            assert!(dest.get((x, y))[3] == expected);
+        }
+    }
+
+    let rgb: Image<f32, Rgb> = image.convert();
+    let dest: Image<f32, Rgb> = rgb.run(chroma_key(key, 0.2, 0.1), None);
+    assert!(dest.get((0, 0)).as_slice() == [0.0, 0.0, 0.0]);
+    assert!(dest.get((9, 9)).as_slice() == rgb.get((9, 9)).as_slice());
+}
+
+#[test]
+fn test_vignette() {
+    let mut image: Image<f32, Rgb> = Image::new((11, 11));
+    image.for_each(|_, mut px| px.copy_from_slice([0.8, 0.8, 0.8]));
+
+    let weak: Image<f32, Rgb> = image.run(vignette(0.25, 0.2), None);
+    let strong: Image<f32, Rgb> = image.run(vignette(0.5, 0.2), None);
+    assert!(weak.get((5, 5)).as_slice() == image.get((5, 5)).as_slice());
+    assert!(strong.get((5, 5)).as_slice() == image.get((5, 5)).as_slice());
+
+    let w = weak.get((0, 0))[0] as f64;
+    let s = strong.get((0, 0))[0] as f64;
+    assert!(w < 0.8 && s < w);
+    assert!(((0.8 - s) / (0.8 - w) - 2.0).abs() < 1e-3);
+    assert!(weak.get((0, 0)).as_slice() == weak.get((10, 10)).as_slice());
+}
+
+#[test]
+fn test_posterize() {
+    let image = Image::<f32, Rgb>::generate((64, 1), |x, _| Pixel::from(vec![x, 1.0 - x, 0.5]));
+    let two: Image<f32, Rgb> = image.run(posterize(2), None);
+    assert!(two.data().iter().all(|x| *x == 0.0 || *x == 1.0));
+
+    let mut values: Image<f32, Rgb> = Image::new((6, 1));
+    for (x, v) in [0.0, 0.16, 0.17, 0.5, 0.84, 1.0].iter().enumerate() {
+        values.set((x, 0), [*v, *v, *v]);
+    }
+    let four: Image<f32, Rgb> = values.run(posterize(4), None);
+    let expected = [0.0, 0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0, 1.0];
+    for (x, e) in expected.iter().enumerate() {
+        assert!((four.get((x, 0))[0] as f64 - e).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_dither() {
+    let image = Image::<f32, Rgb>::generate((64, 16), |x, _| Pixel::from(vec![x, x, x]));
+
+    let mean = |img: &Image<f32, Rgb>, x0: usize| {
+        let mut sum = 0.0;
+        for y in 0..16 {
+            for x in x0..x0 + 8 {
+                sum += img.get((x, y))[0] as f64;
+            }
+        }
+        sum / 128.0
+    };
+
+    let ordered: Image<f32, Rgb> = image.run(dither_ordered(4, 2), None);
+    let mut fs = image.clone();
+    fs.dither_floyd_steinberg(2);
+
+    for dithered in [&ordered, &fs] {
+        assert!(dithered.data().iter().all(|x| *x == 0.0 || *x == 1.0));
+        for x0 in (0..64).step_by(8) {
+            assert!((mean(dithered, x0) - mean(&image, x0)).abs() < 0.1);
+        }
+    }
+}
+
+#[test]
+fn test_color_matrix() {
+    let image = Image::<f32, Rgb>::generate((16, 16), |x, y| Pixel::from(vec![x, y, 0.5]));
+
+    let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let out: Image<f32, Rgb> = image.run(color_matrix(identity, [0.0; 3]), None);
+    for (a, b) in out.data().iter().zip(image.data().iter()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+
+    let gray = Image::<f32, Rgb>::generate((4, 4), |_, _| Pixel::from(vec![0.5, 0.5, 0.5]));
+    let out: Image<f32, Rgb> = gray.run(sepia(), None);
+    let px = out.get_pixel((1, 1));
+    assert!(px[0] > px[1] && px[1] > px[2]);
+}
+
+#[test]
+fn test_region_ops() {
+    let a = Region::new(Point::new(0, 0), Size::new(10, 10));
+    let b = Region::new(Point::new(5, 5), Size::new(10, 10));
+    let c = Region::new(Point::new(20, 20), Size::new(5, 5));
+    let d = Region::new(Point::new(2, 3), Size::new(4, 4));
+
+    // Overlapping
+    assert_eq!(
+        RegionExt::intersect(&a, &b),
+        Some(Region::new(Point::new(5, 5), Size::new(5, 5)))
+    );
+    assert_eq!(
+        RegionExt::union(&a, &b),
+        Region::new(Point::new(0, 0), Size::new(15, 15))
+    );
+    assert!(!a.contains_region(&b));
+
+    // Disjoint
+    assert_eq!(a.intersect(&c), None);
+    assert_eq!(
+        RegionExt::union(&a, &c),
+        Region::new(Point::new(0, 0), Size::new(25, 25))
+    );
+    assert!(!a.contains_region(&c));
+
+    // Nested
+    assert_eq!(a.intersect(&d), Some(d));
+    assert_eq!(RegionExt::union(&a, &d), a);
+    assert!(a.contains_region(&d));
+    assert!(!d.contains_region(&a));
+}
+
+#[test]
+fn test_region_points() {
+    let r = Region::new(Point::new(1, 2), Size::new(3, 2));
+    let points: Vec<_> = r.points().map(|pt| (pt.x, pt.y)).collect();
+    assert_eq!(points, vec![(1, 2), (2, 2), (3, 2), (1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn test_point_size_ops() {
+    let p = Point::new(5, 7);
+    let s = Size::new(2, 3);
+
+    assert_eq!(p + s, Point::new(7, 10));
+    assert_eq!(p - s, Point::new(3, 4));
+    assert_eq!((p - Point::new(1, 2)).to_point(), Point::new(4, 5));
+    assert_eq!(s + s, Size::new(4, 6));
+    assert_eq!(s * 2 - s, s);
+    assert_eq!(Point::from((1, 2)), Point::new(1, 2));
+    assert_eq!(p.to_f64().x, 5.0);
+    assert_eq!(s.to_f64().to_usize(), s);
+
+    assert_eq!(Point::from_f64(1.5, -2.0), Point::new(1, 0));
+    assert_eq!(Size::from_f64(3.9, 4.0), Size::new(3, 4));
+
+    assert_eq!(p.saturating_sub(Size::new(10, 3)), Point::new(0, 4));
+    assert_eq!(
+        p.saturating_add(Size::new(usize::MAX, 1)),
+        Point::new(usize::MAX, 8)
+    );
+    assert_eq!(s.saturating_sub(Size::new(5, 1)), Size::new(0, 2));
+    assert_eq!(
+        s.saturating_add(Size::new(usize::MAX, 0)),
+        Size::new(usize::MAX, 3)
+    );
+}
+
+#[test]
+fn test_map_pixels() {
+    let image = Image::<f32, Rgb>::generate((8, 8), |x, y| Pixel::from(vec![x, y, 0.5]));
+    let gray: Image<u8, Gray> = image.map_pixels(|_, px| Pixel::from(vec![px[0].max(px[1])]));
+    assert_eq!(gray.size(), image.size());
+
+    let x = image.get_pixel((7, 2));
+    let y = gray.get_pixel((7, 2));
+    assert!((y[0] - x[0].max(x[1])).abs() < 1.0 / 255.0);
+    assert_eq!(gray.get((7, 2))[0], 255);
+}
+
+#[test]
+fn test_fill() {
+    let mut image = Image::<u8, Rgba>::new((16, 9));
+    let px = Pixel::from(vec![1.0, 0.5, 0.25, 0.75]);
+    image.fill(&px);
+    let mut expected = [0u8; 4];
+    px.copy_to_slice(&mut expected);
+    assert!(image.data().chunks(4).all(|d| d == expected));
+
+    image.clear();
+    assert!(image.data().chunks(4).all(|d| d == [0, 0, 0, 255]));
+}
+
+#[test]
+fn test_from_raw() {
+    let image = Image::<u8, Rgb>::generate((5, 3), |x, y| Pixel::from(vec![x, y, 1.0]));
+    let data = image.to_vec();
+    assert_eq!(data.len(), 5 * 3 * 3);
+
+    let image2 = Image::<u8, Rgb>::from_raw(image.size(), data.clone()).unwrap();
+    assert_eq!(image2.data(), image.data());
+    assert!(Image::<u8, Rgb>::from_raw((5, 4), data.clone()).is_err());
+
+    let stride = 5 * 3 + 4;
+    let mut padded = vec![0xffu8; stride * 3];
+    for y in 0..3 {
+        padded[y * stride..y * stride + 15].copy_from_slice(&data[y * 15..(y + 1) * 15]);
+    }
+    let image3 = Image::<u8, Rgb>::from_raw_with_stride((5, 3), &padded, stride).unwrap();
+    assert_eq!(image3.data(), image.data());
+    assert!(Image::<u8, Rgb>::from_raw_with_stride((5, 3), &padded, 10).is_err());
+    assert!(Image::<u8, Rgb>::from_raw_with_stride((5, 4), &padded, stride).is_err());
+}
+
+#[test]
+fn test_planar() {
+    let image = Image::<u8, Rgb>::from_raw((2, 2), (0..12).collect()).unwrap();
+    let planar = image.to_planar();
+    assert_eq!(planar, vec![0, 3, 6, 9, 1, 4, 7, 10, 2, 5, 8, 11]);
+
+    let image2 = Image::<u8, Rgb>::from_planar(image.size(), &planar).unwrap();
+    assert_eq!(image2.data(), image.data());
+    assert!(Image::<u8, Rgb>::from_planar((2, 3), &planar).is_err());
+}
+
+#[test]
+fn test_draw() {
+    let white = Pixel::from(vec![1.0, 1.0, 1.0]);
+    let set = |image: &Image<u8, Rgb>| {
+        let mut points = Vec::new();
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                if image.get((x, y))[0] > 0 {
+                    points.push((x, y));
+                }
+            }
+        }
+        points
+    };
+
+    let mut image = Image::<u8, Rgb>::new((10, 10));
+    image.draw_line((2, 4), (6, 4), &white);
+    assert_eq!(set(&image), vec![(2, 4), (3, 4), (4, 4), (5, 4), (6, 4)]);
+
+    let mut image = Image::<u8, Rgb>::new((10, 10));
+    image.draw_line((0, 0), (9, 9), &white);
+    assert_eq!(set(&image), (0..10).map(|i| (i, i)).collect::<Vec<_>>());
+
+    let mut image = Image::<u8, Rgb>::new((10, 10));
+    let roi = Region::new(Point::new(2, 3), Size::new(4, 3));
+    image.draw_rect(roi, &white, false);
+    let border: Vec<_> = roi
+        .points()
+        .filter(|pt| pt.x == 2 || pt.x == 5 || pt.y == 3 || pt.y == 5)
+        .map(|pt| (pt.x, pt.y))
+        .collect();
+    assert_eq!(set(&image), border);
+
+    image.draw_rect(roi, &white, true);
+    let filled: Vec<_> = roi.points().map(|pt| (pt.x, pt.y)).collect();
+    assert_eq!(set(&image), filled);
+
+    let mut image = Image::<u8, Rgb>::new((21, 21));
+    image.draw_circle((10, 10), 5, &white, false);
+    let outline = set(&image);
+    assert!(outline.contains(&(15, 10)) && outline.contains(&(10, 5)));
+    assert!(!outline.contains(&(10, 10)));
+    for (x, y) in &outline {
+        let d = ((*x as f64 - 10.0).powi(2) + (*y as f64 - 10.0).powi(2)).sqrt();
+        assert!((d - 5.0).abs() < 1.0);
+    }
+
+    image.draw_circle((10, 10), 5, &white, true);
+    let filled = set(&image);
+    assert!(filled.contains(&(10, 10)));
+    assert!(filled.len() > outline.len());
+    assert!(!filled.contains(&(16, 10)));
+}
+
+#[test]
+fn test_pipeline_progress() {
+    let image = Image::<f32, Rgb>::generate((16, 16), |x, y| Pixel::from(vec![x, y, 0.5]));
+    let mut dest = image.new_like();
+    let pipeline = Pipeline::new()
+        .then(Kernel::gaussian_3x3())
+        .then(Kernel::sobel());
+
+    let mut values = Vec::new();
+    pipeline.execute_with_progress(&[&image], &mut dest, &mut |p| values.push(p));
+    assert_eq!(values, vec![0.5, 1.0]);
+
+    let mut expected = image.new_like();
+    pipeline.execute(&[&image], &mut expected);
+    assert_eq!(dest.data(), expected.data());
+}
+
+#[test]
+fn test_async_cancel() {
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    let image = Image::<f32, Rgb>::generate((8, 8), |x, y| Pixel::from(vec![x, y, 0.5]));
+    let mut dest = Image::<f32, Rgb>::new(image.size());
+    let f = invert();
+    let mut ctx = Context::from_waker(Waker::noop());
+    let input = [&image];
+
+    let mut fut = f.to_async(AsyncMode::Row, Input::new(&input), &mut dest);
+    assert!(std::pin::Pin::new(&mut fut).poll(&mut ctx).is_pending());
+    let handle = fut.cancel_handle();
+    handle.store(true, std::sync::atomic::Ordering::SeqCst);
+    assert!(fut.is_cancelled());
+    assert!(std::pin::Pin::new(&mut fut).poll(&mut ctx).is_ready());
+    drop(fut);
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let expected = if y == 0 {
+                1.0 - image.get((x, y))[0]
+            } else {
+                0.0
+            };
+            assert_eq!(dest.get((x, y))[0], expected);
+        }
+    }
+
+    let pipeline = Pipeline::new().then(Kernel::gaussian_3x3()).then(invert());
+    let mut dest = Image::<f32, Rgb>::new(image.size());
+    let mut fut = pipeline.to_async(&input, &mut dest);
+    fut.cancel();
+    assert_eq!(std::pin::Pin::new(&mut fut).poll(&mut ctx), Poll::Ready(()));
+    drop(fut);
+    assert!(dest.data().iter().all(|x| *x == 0.0));
+}
+
+#[derive(Debug)]
+struct Gradient;
+
+impl Filter<f32, Gray> for Gradient {
+    fn compute_at(&self, pt: Point, input: &Input<f32, Gray>, dest: &mut DataMut<f32, Gray>) {
+        let mut tmp = [0.0];
+        let mut out = [DataMut::new(dest.as_mut()), DataMut::new(&mut tmp)];
+        self.compute_multi_at(pt, input, &mut out);
+    }
+
+    fn compute_multi_at(
+        &self,
+        pt: Point,
+        input: &Input<f32, Gray>,
+        dest: &mut [DataMut<f32, Gray>],
+    ) {
+        let image = input.images()[0];
+        let x = image.get_f((pt.x + 1, pt.y), 0) - image.get_f(pt, 0);
+        let y = image.get_f((pt.x, pt.y + 1), 0) - image.get_f(pt, 0);
+        dest[0][0] = x as f32;
+        dest[1][0] = y as f32;
+    }
+}
+
+#[test]
+fn test_multi_output() {
+    let image =
+        Image::<f32, Gray>::from_raw((4, 4), (0..16).map(|x| x as f32 / 16.0).collect()).unwrap();
+    let mut dx = image.new_like();
+    let mut dy = image.new_like();
+    Gradient.eval_multi(&[&image], &mut [&mut dx, &mut dy]);
+
+    for y in 0..3 {
+        for x in 0..3 {
+            assert!((dx.get((x, y))[0] - 1.0 / 16.0).abs() < 1e-6);
+            assert!((dy.get((x, y))[0] - 4.0 / 16.0).abs() < 1e-6);
+        }
+    }
+
+    // The default implementation writes the same result to every output
+    let mut a = image.new_like();
+    let mut b = image.new_like();
+    filter::invert().eval_multi(&[&image], &mut [&mut a, &mut b]);
+    assert_eq!(a.data(), b.data());
+    assert_eq!(a.data()[1], 1.0 - image.data()[1]);
+}
+
+#[test]
+fn test_reduce() {
+    let image = Image::<f32, Gray>::generate((32, 16), |x, y| Pixel::from(vec![x * y]));
+    let stats = image.statistics();
+    let n = (image.width() * image.height()) as f64;
+
+    let sum = image.reduce(0.0, |acc, _, px| acc + px[0]);
+    assert!((sum / n - stats[0].mean).abs() < 1e-9);
+
+    let count = image.reduce(0, |acc, pt, _| acc + (pt.x == 0) as usize);
+    assert_eq!(count, image.height());
+
+    #[cfg(feature = "parallel")]
+    {
+        let par_sum = image.par_reduce(|| 0.0, |acc, _, px| acc + px[0], |a, b| a + b);
+        assert!((par_sum / n - stats[0].mean).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_dominant_colors_subsampled() {
+    let a = [0.9, 0.2, 0.1];
+    let b = [0.1, 0.3, 0.8];
+    let mut image: Image<f32, Rgb> = Image::new((512, 300));
+    image.for_each(|pt, mut px| {
+        px.copy_from_slice(if pt.y < 100 { a } else { b });
+    });
+
+    let dominant = image.dominant_colors(2, 10);
+    assert_eq!(dominant.len(), 2);
+    for (color, weight) in [(a, 1.0 / 3.0), (b, 2.0 / 3.0)] {
+        assert!(dominant.iter().any(|(px, w)| {
+            (w - weight).abs() < 0.01 && (0..3).all(|c| (px[c] - color[c] as f64).abs() < 1e-3)
+        }));
+    }
+}
+
+#[test]
+fn test_quantize_to_palette() {
+    let image = Image::<u8, Rgb>::generate((64, 8), |x, _| Pixel::from(vec![x, x * 0.5, 0.2]));
+    let palette = [
+        Pixel::from(vec![0.0, 0.0, 0.0]),
+        Pixel::from(vec![1.0, 0.5, 0.2]),
+    ];
+    let out = image.quantize_to_palette(&palette);
+
+    let entries: Vec<Vec<u8>> = palette
+        .iter()
+        .map(|p| {
+            let mut data = vec![0u8; 3];
+            p.copy_to_slice(&mut data);
+            data
+        })
+        .collect();
+    assert!(out
+        .data()
+        .chunks(3)
+        .all(|px| entries.iter().any(|e| e == px)));
+    assert_eq!(out.get((0, 0)).as_slice(), entries[0].as_slice());
+    assert_eq!(out.get((63, 0)).as_slice(), entries[1].as_slice());
+}
+
+#[test]
+fn test_indexed_image() {
+    let colors = [[255u8, 0, 0], [0, 255, 0], [20, 40, 200], [250, 250, 250]];
+    let mut image: Image<u8, Rgb> = Image::new((16, 16));
+    image.for_each(|pt, mut px| {
+        px.copy_from_slice(colors[(pt.y / 8) * 2 + pt.x / 8]);
+    });
+
+    let indexed = IndexedImage::from_rgb_quantized(&image, 4).unwrap();
+    assert_eq!(indexed.palette().len(), 4);
+    assert_eq!(indexed.size(), image.size());
+
+    let rgb = indexed.to_rgb();
+    for (a, b) in rgb.data().iter().zip(image.data()) {
+        assert!((*a as i32 - *b as i32).abs() <= 1);
+    }
+
+    assert!(IndexedImage::new(indexed.indices().clone(), indexed.palette()[..2].to_vec()).is_err());
+    assert!(IndexedImage::from_rgb_quantized(&image, 0).is_err());
+}
+
+#[test]
+fn test_filter_spec() {
+    let image = Image::<f32, Rgb>::generate((16, 16), |x, y| Pixel::from(vec![x, y, 0.5]));
+
+    let mut expected = image.new_like();
+    Pipeline::new()
+        .then(brightness(1.5))
+        .then(invert())
+        .execute(&[&image], &mut expected);
+
+    let pipeline: Pipeline<f32, Rgb> =
+        Pipeline::from_specs(&[("brightness", &[1.5]), ("invert", &[])]).unwrap();
+    let mut dest = image.new_like();
+    pipeline.execute(&[&image], &mut dest);
+    assert_eq!(dest.data(), expected.data());
+
+    let f = parse_spec::<f32, Rgb, f32, Rgb>("brightness=1.5").unwrap();
+    let mut single = image.new_like();
+    f.eval(&[&image], &mut single);
+    let single2: Image<f32, Rgb> = image.run(brightness(1.5), None);
+    assert_eq!(single.data(), single2.data());
+
+    assert!(from_spec::<f32, Rgb, f32, Rgb>("unknown", &[]).is_none());
+    assert!(from_spec::<f32, Rgb, f32, Rgb>("brightness", &[]).is_none());
+    assert!(parse_spec::<f32, Rgb, f32, Rgb>("contrast=abc").is_none());
+    assert!(Pipeline::<f32, Rgb>::from_specs(&[("invert", &[]), ("nope", &[])]).is_none());
+}
+
+#[test]
+fn test_diff_ssim() {
+    let image = Image::<f32, Rgb>::generate((32, 32), |x, y| {
+        Pixel::from(vec![x, y, ((x * 20.0).sin() + 1.0) * 0.5])
+    });
+    let diff = image.diff(&image).unwrap();
+    assert!(diff.data().iter().all(|x| *x == 0.0));
+    assert!((image.ssim(&image).unwrap() - 1.0).abs() < 1e-9);
+
+    let inverted: Image<f32, Rgb> = image.run(invert(), None);
+    let diff = image.diff(&inverted).unwrap();
+    assert!(diff.data().iter().any(|x| *x > 0.1));
+    assert!(image.ssim(&inverted).unwrap() < 0.5);
+
+    let small = Image::<f32, Rgb>::new((16, 16));
+    assert!(image.diff(&small).is_err());
+    assert!(image.ssim(&small).is_err());
+}
+
+#[test]
+fn test_mse_psnr() {
+    let a = Image::<u8, Rgb>::generate((4, 4), |x, y| Pixel::from(vec![x, y, 0.5]));
+    assert_eq!(a.mse(&a).unwrap(), 0.0);
+    assert_eq!(a.psnr(&a).unwrap(), f64::INFINITY);
+
+    let mut b = a.clone();
+    b.data_mut()[5] ^= 1;
+    let mse = (1.0f64 / 255.0).powi(2) / 48.0;
+    assert!((b.mse(&a).unwrap() - mse).abs() < 1e-15);
+    let psnr = b.psnr(&a).unwrap();
+    assert!(psnr.is_finite());
+    assert!((psnr - 10.0 * (1.0 / mse).log10()).abs() < 1e-9);
+
+    assert!(a.mse(&Image::new((4, 3))).is_err());
+}
+
+#[test]
+fn test_box_blur() {
+    let image = Image::<f32, Rgb>::generate((37, 23), |x, y| {
+        Pixel::from(vec![x, y, ((x * 13.0).sin() * (y * 7.0).cos() + 1.0) * 0.5])
+    });
+
+    for radius in [0, 1, 4, 12] {
+        let n = 2 * radius + 1;
+        let dense = Kernel::create(n, n, |_, _| 1.0 / (n * n) as f64);
+        let expected: Image<f32, Rgb> = image.run(dense, None);
+        let fast: Image<f32, Rgb> = image.run(box_blur(radius), None);
+        for (a, b) in fast.data().iter().zip(expected.data()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+
+        let mut direct = image.new_like();
+        Pipeline::new()
+            .then(box_blur(radius))
+            .execute(&[&image], &mut direct);
+        for (a, b) in direct.data().iter().zip(expected.data()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn test_fast_gaussian() {
+    let image = Image::<f32, Rgb>::generate((48, 40), |x, y| {
+        Pixel::from(vec![
+            x,
+            y,
+            ((x * 17.0).sin() * (y * 11.0).cos() + 1.0) * 0.5,
+        ])
+    });
+
+    let sigma = 4.0;
+    let expected: Image<f32, Rgb> = image.run(Kernel::gaussian(25, sigma), None);
+    let fast: Image<f32, Rgb> = image.run(fast_gaussian(sigma, 3), None);
+    let mae = fast
+        .data()
+        .iter()
+        .zip(expected.data())
+        .map(|(a, b)| (a - b).abs() as f64)
+        .sum::<f64>()
+        / fast.data().len() as f64;
+    assert!(mae < 0.01);
+
+    // Per-pixel evaluation in a pipeline matches the box passes
+    let mut direct = image.new_like();
+    Pipeline::new()
+        .then(fast_gaussian(sigma, 3))
+        .execute(&[&image], &mut direct);
+    for (a, b) in direct.data().iter().zip(fast.data()) {
+        assert!((a - b).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_resample_2x() {
+    let image = Image::<u8, Rgb>::generate((7, 4), |_, _| Pixel::from(vec![0.2, 0.4, 0.6]));
+    let expected = image.get((0, 0)).as_slice().to_vec();
+
+    let down = image.downsample_2x();
+    assert_eq!(down.size(), Size::new(3, 2));
+    assert!(down.data().chunks(3).all(|px| px == expected));
+
+    let up = image.upsample_2x();
+    assert_eq!(up.size(), Size::new(14, 8));
+    assert!(up.data().chunks(3).all(|px| px == expected));
+
+    let ramp = Image::<f32, Gray>::from_raw((2, 1), vec![0.0, 1.0]).unwrap();
+    let up = ramp.upsample_2x();
+    assert_eq!(up.data(), &[0.0, 0.25, 0.75, 1.0, 0.0, 0.25, 0.75, 1.0]);
+    assert_eq!(up.downsample_2x().data(), &[0.125, 0.875]);
+
+    let empty = Image::<u8, Rgb>::new((0, 3));
+    assert!(empty.upsample_2x().size() == Size::new(0, 6));
+    assert!(Image::<u8, Rgb>::new((3, 0)).upsample_2x().size() == Size::new(6, 0));
+    assert!(empty.downsample_2x().size() == Size::new(0, 1));
+    assert!(Image::<u8, Rgb>::new((4, 0)).downsample_2x().size() == Size::new(2, 0));
+    assert!(Image::<u8, Rgb>::new((0, 0)).downsample_2x().size() == Size::new(0, 0));
+}
+
+#[test]
+fn test_sample() {
+    let image = Image::<f32, Gray>::from_raw((2, 2), vec![0.0, 0.2, 0.4, 1.0]).unwrap();
+
+    let px = image.sample(0.5, 0.5, InterpolationMode::Bilinear);
+    assert!((px[0] - 0.4).abs() < 1e-6);
+    let px = image.sample(0.25, 0.0, InterpolationMode::Bilinear);
+    assert!((px[0] - 0.05).abs() < 1e-6);
+
+    // Clamped at the edges
+    assert!((image.sample(-3.0, 5.0, InterpolationMode::Bilinear)[0] - 0.4).abs() < 1e-6);
+    assert!((image.sample(0.6, 0.2, InterpolationMode::Nearest)[0] - 0.2).abs() < 1e-6);
+
+    // Bicubic interpolation passes through the original pixel values
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        let px = image.sample(x as f64, y as f64, InterpolationMode::Bicubic);
+        assert!((px[0] - image.get((x, y))[0] as f64).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_warp() {
+    let image = Image::<f32, Rgb>::generate((24, 16), |x, y| Pixel::from(vec![x, y, x * y]));
+
+    let identity: Image<f32, Rgb> = image.run(warp(|pt| (pt.x as f64, pt.y as f64)), None);
+    for (a, b) in identity.data().iter().zip(image.data()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+
+    let shifted: Image<f32, Rgb> = image.run(warp(|pt| (pt.x as f64 + 0.5, pt.y as f64)), None);
+    let expected = (image.get((3, 2))[0] + image.get((4, 2))[0]) / 2.0;
+    assert!((shifted.get((3, 2))[0] - expected).abs() < 1e-6);
+}
+
+#[test]
+fn test_add_saturating() {
+    let mut a = Image::<u8, Rgb>::new((4, 4));
+    let mut b = Image::<u8, Rgb>::new((4, 4));
+    a.fill(&Pixel::from(vec![0.8, 0.8, 0.8]));
+    b.fill(&Pixel::from(vec![0.7, 0.2, 0.1]));
+
+    a.add_saturating(&b).unwrap();
+    let px = a.get_pixel((1, 1));
+    assert_eq!(px[0], 1.0);
+    assert_eq!(a.get((2, 2))[0], 255);
+
+    a.sub_saturating(&b).unwrap();
+    a.sub_saturating(&b).unwrap();
+    let px = a.get_pixel((0, 0));
+    assert_eq!(px[0], 0.0);
+
+    let c = Image::<u8, Rgb>::new((2, 2));
+    assert!(a.add_saturating(&c).is_err());
+}
+
+#[test]
+fn test_checked_get() {
+    let mut image = Image::<u8, Rgb>::new((3, 2));
+    image.set((2, 1), [1, 2, 3]);
+
+    assert_eq!(image.checked_get((2, 1)).unwrap().as_ref(), &[1, 2, 3]);
+    assert!(image.checked_get((3, 1)).is_none());
+    assert!(image.checked_get((0, 2)).is_none());
+
+    assert!(image.checked_get_pixel((0, 0)).is_some());
+    assert!(image.checked_get_pixel((5, 5)).is_none());
+}
+
+#[test]
+fn test_bit_image() {
+    let mut mask = BitImage::new((13, 7));
+    assert_eq!(mask.buffer().len(), (13usize * 7).div_ceil(8));
+
+    mask.set((0, 0), true);
+    mask.set((12, 6), true);
+    mask.set((5, 3), true);
+    mask.set((5, 3), false);
+    mask.set((20, 20), true);
+    assert!(mask.get((0, 0)));
+    assert!(mask.get((12, 6)));
+    assert!(!mask.get((5, 3)));
+    assert!(!mask.get((20, 20)));
+    assert_eq!(mask.count(), 2);
+
+    let image: Image<Bit, Gray> = mask.to_image();
+    assert_eq!(image.get((12, 6))[0], Bit::new(true));
+    assert_eq!(image.get_f((0, 0), 0), 1.0);
+    assert_eq!(image.get_f((1, 0), 0), 0.0);
+    assert_eq!(BitImage::from_image(&image), mask);
+
+    let gray: Image<u8, Gray> = image.convert();
+    assert_eq!(gray.get((12, 6))[0], 255);
+    assert_eq!(BitImage::from_image(&gray), mask);
+    assert_eq!(Bit::bits(), 8);
+}
+
+#[test]
+fn test_convert_rows() {
+    let image = Image::<u8, Rgba>::generate((67, 31), |x, y| {
+        Pixel::from(vec![x, y, (x + y) / 2.0, 1.0 - x * 0.5])
+    });
+
+    let a: Image<f32, Srgb> = image.convert();
+    let b: Image<f32, Srgb> = image.run(filter::convert(), None);
+    assert!(a == b);
+
+    let a: Image<u16, Gray> = image.convert();
+    let b: Image<u16, Gray> = image.run(filter::convert(), None);
+    assert!(a == b);
+
+    let a: Image<u8, Hsv> = a.convert();
+    let b: Image<u8, Hsv> = b.run(filter::convert(), None);
+    assert!(a == b);
+
+    let a: Image<f64, Cmyk> = image.convert();
+    let b: Image<f64, Cmyk> = image.run(filter::convert(), None);
+    assert!(a == b);
+}
+
+#[test]
+fn test_auto_contrast() {
+    let mut image = Image::<u8, Gray>::new((100, 1));
+    for x in 0..100 {
+        image.set_f((x, 0), 0, 0.4 + 0.2 * x as f64 / 99.0);
+    }
+
+    let mut full = image.clone();
+    full.auto_contrast(0.0, 100.0);
+    assert_eq!(full.get((0, 0))[0], 0);
+    assert_eq!(full.get((99, 0))[0], 255);
+
+    image.auto_contrast(5.0, 95.0);
+    let data = image.data();
+    assert_eq!(*data.iter().min().unwrap(), 0);
+    assert_eq!(*data.iter().max().unwrap(), 255);
+    let low = data.iter().filter(|x| **x == 0).count();
+    let high = data.iter().filter(|x| **x == 255).count();
+    assert!(low >= 5, "{low}");
+    assert!(high >= 5, "{high}");
+    assert!(data[50] > 100 && data[50] < 155);
+}
+
+#[test]
+fn test_srgb_transfer() {
+    let mut image = Image::<f64, Rgba>::new((64, 1));
+    for x in 0..64 {
+        let v = x as f64 / 63.0;
+        image.set((x, 0), [v, v * 0.5, v * v, 0.5]);
+    }
+    let linear = image.clone();
+
+    image.to_srgb();
+    assert_eq!(image.get((10, 0))[3], 0.5);
+    assert!(image.get((32, 0))[0] > linear.get((32, 0))[0]);
+    image.to_linear();
+    for (a, b) in image.data().iter().zip(linear.data().iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+
+    // Near zero the transfer function is linear
+    let mut px = Pixel::<Rgb>::from(vec![0.001, 0.002, 0.003]);
+    px.to_srgb();
+    assert!((px[0] - 0.01292).abs() < 1e-12);
+    assert!((px[2] - 0.03876).abs() < 1e-12);
+
+    let mut px = Pixel::<Rgb>::from(vec![0.5, 1.0, 0.0]);
+    px.to_srgb();
+    assert!((px[0] - 0.735357).abs() < 1e-6);
+    assert!((px[1] - 1.0).abs() < 1e-12);
+    assert_eq!(px[2], 0.0);
+}
+
+#[test]
+fn test_srgb_color() {
+    let mut image = Image::<f32, Srgb>::new((2, 2));
+    image.fill(&Pixel::from(vec![0.5, 0.5, 0.5]));
+    let linear: Image<f32, Rgb> = image.convert();
+    let px = linear.get_pixel((1, 1));
+    assert!((px[0] - 0.214041).abs() < 1e-5);
+    assert!(px[0] < 0.5);
+
+    let back: Image<f32, Srgb> = linear.convert();
+    assert!((back.get_pixel((0, 0))[1] - 0.5).abs() < 1e-6);
+
+    let px = Pixel::<Srgba>::from(vec![0.5, 0.02, 1.0, 0.5]);
+    let rgb: Pixel<Rgb> = px.convert();
+    assert!((rgb[0] - 0.214041 * 0.5).abs() < 1e-5);
+    assert!((rgb[1] - 0.02 / 12.92 * 0.5).abs() < 1e-9);
+    assert!((rgb[2] - 0.5).abs() < 1e-9);
+
+    let srgba: Pixel<Srgba> = Pixel::<Rgb>::from(vec![0.214041, 0.0, 1.0]).convert();
+    assert!((srgba[0] - 0.5).abs() < 1e-5);
+    assert_eq!(srgba[3], 1.0);
+}
+
+#[test]
+fn test_try_crop() {
+    let image = Image::<u8, Rgb>::generate((10, 8), |x, y| Pixel::from(vec![x, y, 1.0]));
+
+    let roi = Region::new(Point::new(6, 4), Size::new(4, 4));
+    let a = image.try_crop(roi).unwrap();
+    assert_eq!(a.size(), Size::new(4, 4));
+    assert_eq!(a.get((3, 3)).as_ref(), image.get((9, 7)).as_ref());
+    assert_eq!(a.get((0, 0)).as_ref(), image.get((6, 4)).as_ref());
+
+    let roi = Region::new(Point::new(8, 6), Size::new(4, 4));
+    assert!(image.try_crop(roi).is_err());
+    assert!(image
+        .try_crop(Region::new(Point::new(0, 0), Size::new(0, 4)))
+        .is_err());
+
+    let b = image.crop(roi);
+    assert_eq!(b.size(), Size::new(4, 4));
+    assert_eq!(b.get((1, 1)).as_ref(), image.get((9, 7)).as_ref());
+    assert_eq!(b.get((2, 1)).as_ref(), &[0, 0, 0]);
+    assert_eq!(b.get((0, 2)).as_ref(), &[0, 0, 0]);
+}
+
+#[test]
+fn test_meta_attrs() {
+    let mut image = Image::<u8, Rgb>::new((8, 8));
+    image.meta.set_attr("Artist", "someone");
+    image.meta.set_attr("Orientation", 1);
+
+    let a: Image<u8, Rgb> = image.run(filter::invert(), None);
+    assert_eq!(a.meta.attr("Artist"), Some(&MetaAttr::from("someone")));
+    assert_eq!(a.meta.attr("Orientation"), Some(&MetaAttr::Int(1)));
+
+    let b: Image<f32, Gray> = a.convert();
+    assert_eq!(b.meta.attrs, image.meta.attrs);
+    assert_eq!(image.new_like().meta.attrs, image.meta.attrs);
+
+    let mut output = Meta::new((8, 8));
+    output.set_attr("Orientation", 3);
+    let c: Image<u8, Rgb> = image.run(filter::invert(), Some(output));
+    assert_eq!(c.meta.attr("Orientation"), Some(&MetaAttr::Int(3)));
+    assert_eq!(c.meta.attr("Artist"), Some(&MetaAttr::from("someone")));
+
+    let mut d = image.new_like();
+    d.meta.set_attr("Exposure", 0.5f32);
+    assert_eq!(d.meta.attr("Exposure"), Some(&MetaAttr::Float(0.5)));
+    assert!(d == image);
+}
@@ -44,7 +44,9 @@ pub fn width(text: impl AsRef<str>, font: &Font, size: f32) -> usize {
 }
 
 impl<T: Type, C: Color> Image<T, C> {
-    /// Draw text on image
+    /// Draw text on image, `pos` is the left edge of the baseline of the first glyph. Glyph
+    /// coverage is used to blend `color` with the existing pixels, pixels outside of the image
+    /// are skipped
     pub fn draw_text<'a>(
         &mut self,
         text: impl AsRef<str>,
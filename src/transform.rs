@@ -28,6 +28,143 @@ impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Transform {
     }
 }
 
+/// Perspective (homography) transform using a 3x3 matrix, the matrix maps source coordinates to
+/// destination coordinates. When used as a `Filter` each output pixel is mapped back into the
+/// input image using the inverse matrix and sampled with bilinear interpolation, output pixels
+/// that map outside of the input image are set to zero.
+///
+/// For example, to straighten a photographed document into a 850x1100 image given the location
+/// of its four corners:
+///
+/// ```rust,no_run
+/// use image2::*;
+///
+/// let photo = Image::<f32, Rgb>::open("document.jpg").unwrap();
+/// let corners = [(112, 80), (980, 130), (1010, 1240), (70, 1190)].map(Point::from);
+/// let rect = [(0, 0), (849, 0), (849, 1099), (0, 1099)].map(Point::from);
+/// let perspective = Perspective::from_quad_to_quad(corners, rect).unwrap();
+/// let mut dest = Image::<f32, Rgb>::new((850, 1100));
+/// perspective.eval(&[&photo], &mut dest);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Perspective {
+    matrix: [[f64; 3]; 3],
+    inverse: [[f64; 3]; 3],
+}
+
+impl Perspective {
+    /// Create a new perspective transform, returns `None` if the matrix is not invertible
+    pub fn new(matrix: [[f64; 3]; 3]) -> Option<Perspective> {
+        let m = &matrix;
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+
+        let mut inverse = [[0.0; 3]; 3];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                // Transposed cofactor matrix
+                let (r0, r1) = ((j + 1) % 3, (j + 2) % 3);
+                let (c0, c1) = ((i + 1) % 3, (i + 2) % 3);
+                *x = (m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]) / det;
+            }
+        }
+
+        Some(Perspective { matrix, inverse })
+    }
+
+    /// Find the perspective transform that maps each point in `src` to the point at the same
+    /// index in `dst`, returns `None` if the points are degenerate (for example when three of
+    /// them are on the same line)
+    pub fn from_quad_to_quad(src: [Point; 4], dst: [Point; 4]) -> Option<Perspective> {
+        // Solve for the first 8 matrix entries, the last entry is fixed at 1
+        let mut a = [[0.0; 9]; 8];
+        for i in 0..4 {
+            let (x, y) = (src[i].x as f64, src[i].y as f64);
+            let (u, v) = (dst[i].x as f64, dst[i].y as f64);
+            a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+
+        // Gaussian elimination with partial pivoting
+        for col in 0..8 {
+            let pivot = (col..8).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+
+            let pivot = a[col];
+            for (i, row) in a.iter_mut().enumerate() {
+                if i != col {
+                    let f = row[col] / pivot[col];
+                    for (x, p) in row.iter_mut().zip(&pivot).skip(col) {
+                        *x -= f * p;
+                    }
+                }
+            }
+        }
+
+        let h = |i: usize| a[i][8] / a[i][i];
+        Perspective::new([[h(0), h(1), h(2)], [h(3), h(4), h(5)], [h(6), h(7), 1.0]])
+    }
+
+    /// Get the transform matrix
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    /// Get the inverse transform
+    pub fn inverse(&self) -> Perspective {
+        Perspective {
+            matrix: self.inverse,
+            inverse: self.matrix,
+        }
+    }
+
+    fn apply(m: &[[f64; 3]; 3], x: f64, y: f64) -> Option<(f64, f64)> {
+        let w = m[2][0] * x + m[2][1] * y + m[2][2];
+        if w.abs() < f64::EPSILON {
+            return None;
+        }
+        Some((
+            (m[0][0] * x + m[0][1] * y + m[0][2]) / w,
+            (m[1][0] * x + m[1][1] * y + m[1][2]) / w,
+        ))
+    }
+
+    /// Map a point from source to destination coordinates, returns `None` if the point maps to
+    /// infinity
+    pub fn transform_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        Self::apply(&self.matrix, x, y)
+    }
+}
+
+impl<T: Type, C: Color, U: Type, D: Color> Filter<T, C, U, D> for Perspective {
+    fn schedule(&self) -> Schedule {
+        Schedule::Image
+    }
+
+    fn compute_at(&self, pt: Point, input: &Input<T, C>, px: &mut DataMut<U, D>) {
+        let image = input.images()[0];
+        let (w, h) = (image.width() as f64, image.height() as f64);
+        match Self::apply(&self.inverse, pt.x as f64, pt.y as f64) {
+            Some((x, y)) if x > -0.5 && y > -0.5 && x < w - 0.5 && y < h - 0.5 => image
+                .sample(x, y, InterpolationMode::Bilinear)
+                .copy_to_slice(px),
+            _ => {
+                for x in px.as_mut() {
+                    *x = U::from_f64(0.0);
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{filter::*, Filter, Image, Rgb};
@@ -73,4 +210,35 @@ mod test {
         resize(a.size(), a.size() * 2).eval(&[&a], &mut dest1);
         assert!(dest0 == dest1);
     }
+
+    #[test]
+    fn test_perspective() {
+        use crate::{transform::Perspective, Point};
+
+        let src = [(0, 0), (10, 0), (10, 10), (0, 10)].map(Point::from);
+        let dst = [(2, 1), (17, 3), (14, 12), (1, 9)].map(Point::from);
+        let p = Perspective::from_quad_to_quad(src, dst).unwrap();
+        for (a, b) in src.iter().zip(&dst) {
+            let (x, y) = p.transform_point(a.x as f64, a.y as f64).unwrap();
+            assert!((x - b.x as f64).abs() < 1e-9 && (y - b.y as f64).abs() < 1e-9);
+            let (x, y) = p.inverse().transform_point(x, y).unwrap();
+            assert!((x - a.x as f64).abs() < 1e-9 && (y - a.y as f64).abs() < 1e-9);
+        }
+
+        let image =
+            Image::<f32, Rgb>::generate((11, 11), |x, y| crate::Pixel::from(vec![x, y, 1.0]));
+        let mut dest = Image::<f32, Rgb>::new((20, 15));
+        p.eval(&[&image], &mut dest);
+        for (a, b) in src.iter().zip(&dst) {
+            let expected = image.get(*a);
+            let actual = dest.get(*b);
+            for c in 0..3 {
+                assert!((expected[c] - actual[c]).abs() < 1e-5);
+            }
+        }
+        assert_eq!(dest.get((19, 0))[2], 0.0);
+
+        let line = [(0, 0), (1, 1), (2, 2), (0, 5)].map(Point::from);
+        assert!(Perspective::from_quad_to_quad(line, dst).is_none());
+    }
 }
@@ -252,3 +252,55 @@ impl Type for f64 {
         f
     }
 }
+
+/// Single-bit value for binary masks, `Image<Bit, Gray>` stores one byte per pixel, see
+/// `BitImage` for a packed representation
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bit(u8);
+
+impl Bit {
+    /// Create a new `Bit`
+    pub fn new(b: bool) -> Bit {
+        Bit(b as u8)
+    }
+
+    /// Returns true when the bit is set
+    pub fn is_set(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl From<bool> for Bit {
+    fn from(b: bool) -> Bit {
+        Bit::new(b)
+    }
+}
+
+impl From<Bit> for bool {
+    fn from(b: Bit) -> bool {
+        b.is_set()
+    }
+}
+
+impl Type for Bit {
+    const MIN: f64 = 0.0;
+    const MAX: f64 = 1.0;
+    const BASE: io::BaseType = io::BaseType::UInt8;
+
+    fn to_f64(&self) -> f64 {
+        if self.is_set() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn from_f64(f: f64) -> Self {
+        Bit::new(f >= 0.5)
+    }
+
+    fn is_float() -> bool {
+        false
+    }
+}
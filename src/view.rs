@@ -0,0 +1,139 @@
+use crate::*;
+
+/// A borrowed, read-only region of an image
+#[derive(Clone, Copy)]
+pub struct ImageView<'a, T: Type, C: Color> {
+    image: &'a Image<T, C>,
+    roi: Region,
+}
+
+impl<T: Type, C: Color> std::fmt::Debug for ImageView<'_, T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ImageView")
+            .field("image_size", &self.image.size())
+            .field("roi", &self.roi)
+            .finish()
+    }
+}
+
+impl<'a, T: Type, C: Color> ImageView<'a, T, C> {
+    /// Create a new view, `roi` is clamped to the bounds of `image`
+    pub fn new(image: &'a Image<T, C>, roi: Region) -> ImageView<'a, T, C> {
+        let roi = roi
+            .intersection(&Region::from_size(image.size()))
+            .unwrap_or_default();
+        ImageView { image, roi }
+    }
+
+    /// Get the parent image
+    pub fn image(&self) -> &'a Image<T, C> {
+        self.image
+    }
+
+    /// Get the region of the parent image covered by the view
+    pub fn region(&self) -> Region {
+        self.roi
+    }
+
+    /// View width
+    pub fn width(&self) -> usize {
+        self.roi.width()
+    }
+
+    /// View height
+    pub fn height(&self) -> usize {
+        self.roi.height()
+    }
+
+    /// View size
+    pub fn size(&self) -> Size {
+        self.roi.size
+    }
+
+    /// Returns true when the given point is inside the view
+    pub fn in_bounds(&self, pt: impl Into<Point>) -> bool {
+        let pt = pt.into();
+        pt.x < self.width() && pt.y < self.height()
+    }
+
+    fn parent_point(&self, pt: impl Into<Point>) -> Point {
+        let pt = pt.into();
+        Point::new(pt.x + self.roi.origin.x, pt.y + self.roi.origin.y)
+    }
+
+    /// Get data at the specified point, relative to the view origin
+    ///
+    /// Panics when `pt` is outside of the view, see `ImageView::checked_get`
+    pub fn get(&self, pt: impl Into<Point>) -> Data<'a, T, C> {
+        let pt = pt.into();
+        match self.checked_get(pt) {
+            Some(data) => data,
+            None => panic!("point {:?} is outside of the view {:?}", pt, self.roi),
+        }
+    }
+
+    /// Get pixel at the specified point, relative to the view origin
+    pub fn get_pixel(&self, pt: impl Into<Point>) -> Pixel<C> {
+        self.get(pt).to_pixel()
+    }
+
+    /// Get data at the specified point, relative to the view origin, returning `None` when `pt`
+    /// is outside of the view
+    pub fn checked_get(&self, pt: impl Into<Point>) -> Option<Data<'a, T, C>> {
+        let pt = pt.into();
+        if !self.in_bounds(pt) {
+            return None;
+        }
+
+        Some(self.image.get(self.parent_point(pt)))
+    }
+
+    /// Get pixel at the specified point, relative to the view origin, returning `None` when `pt`
+    /// is outside of the view
+    pub fn checked_get_pixel(&self, pt: impl Into<Point>) -> Option<Pixel<C>> {
+        self.checked_get(pt).map(|data| data.to_pixel())
+    }
+
+    fn row_slice(&self, y: usize) -> &'a [T] {
+        assert!(
+            y < self.height(),
+            "row {} is outside of the view {:?}",
+            y,
+            self.roi
+        );
+        let start = self.image.meta.index(self.parent_point((0, y)));
+        &self.image.data()[start..start + self.width() * C::CHANNELS]
+    }
+
+    /// Get a single row of the view
+    ///
+    /// Panics when `y` is outside of the view
+    pub fn row(&self, y: usize) -> Data<'a, T, C> {
+        Data::new(self.row_slice(y))
+    }
+
+    /// Iterate over the rows of the view
+    pub fn rows(&self) -> impl 'a + Iterator<Item = (usize, &'a [T])> {
+        let view = *self;
+        (0..self.height()).map(move |y| (y, view.row_slice(y)))
+    }
+
+    /// Iterate over each pixel of the view, points are relative to the view origin
+    pub fn iter(&self) -> impl 'a + Iterator<Item = (Point, Data<'a, T, C>)> {
+        self.rows().flat_map(|(y, row)| {
+            row.chunks(C::CHANNELS)
+                .map(Data::new)
+                .enumerate()
+                .map(move |(x, d)| (Point::new(x, y), d))
+        })
+    }
+
+    /// Copy the view into a new image
+    pub fn to_image(&self) -> Image<T, C> {
+        let mut dest = Image::new(self.size());
+        for (y, row) in self.rows() {
+            dest.row_mut(y).copy_from_slice(row);
+        }
+        dest
+    }
+}